@@ -0,0 +1,178 @@
+use std::io::{self, Write};
+use grid::{Grid, Point};
+use recording::{Cell, Recording, Style, REGION_COLOURS};
+
+/// The styles in the order of their colours in the palette of the images.
+const STYLES: [Style; 6] = [Style::Floor, Style::Wall, Style::Box, Style::Robot, Style::Visited, Style::Marker];
+
+fn palette() -> Vec<u8> {
+    STYLES.iter()
+          .copied()
+          .chain((0..REGION_COLOURS.len()).map(Style::Region))
+          .flat_map(Style::rgb)
+          .collect()
+}
+
+fn palette_index(style: Style) -> u8 {
+    match style {
+        Style::Region(region) => (STYLES.len() + region % REGION_COLOURS.len()) as u8,
+        _ => STYLES.iter().position(|known| *known == style).unwrap() as u8,
+    }
+}
+
+/// Writes a frame as a PNG image in which every cell is a square of `scale`
+/// by `scale` pixels in the colour of its style.
+pub fn write_png(cells: &Grid<Cell>, scale: u32, out: impl Write) -> io::Result<()> {
+    let (width, height) = (cells.width() as u32 * scale, cells.height() as u32 * scale);
+    let mut encoder = png::Encoder::new(out, width, height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette());
+
+    let pixels = pixels(cells, scale, &Area::all(cells));
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&pixels).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Writes the `frames` of a recording as an animated GIF that loops forever.
+/// Only the part of a frame that changed is stored, and frames that change
+/// nothing just add to how long the one before is shown, which keeps long
+/// recordings small.
+pub fn write_gif(recording: &Recording,
+                 frames: &[usize],
+                 scale: u32,
+                 frames_per_second: f64,
+                 out: impl Write) -> io::Result<()> {
+    let Some(&first) = frames.first() else {
+        return Ok(());
+    };
+    let mut cells = recording.frame(first);
+    let (width, height) = (cells.width() as u32 * scale, cells.height() as u32 * scale);
+    if width > u16::MAX as u32 || height > u16::MAX as u32 {
+        return Err(io::Error::other(format!("{} by {} pixels is too large for a GIF", width, height)));
+    }
+
+    let mut encoder = gif::Encoder::new(out, width as u16, height as u16, &palette()).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+    // In hundredths of a second, browsers slow down anything below 2
+    let delay = (100.0 / frames_per_second).round().clamp(2.0, u16::MAX as f64) as u16;
+
+    let mut shown = cells.clone();
+    let mut pending = frame(&cells, scale, Area::all(&cells), delay);
+    let mut index = first;
+    for &next in &frames[1..] {
+        if next == index + 1 {
+            recording.advance(next, &mut cells);
+        } else {
+            cells = recording.frame(next);
+        }
+        index = next;
+
+        match Area::changed(&shown, &cells) {
+            Some(area) => {
+                encoder.write_frame(&pending).map_err(io::Error::other)?;
+                pending = frame(&cells, scale, area, delay);
+                shown = cells.clone();
+            }
+            None => pending.delay = pending.delay.saturating_add(delay),
+        }
+    }
+    encoder.write_frame(&pending).map_err(io::Error::other)
+}
+
+/// A rectangle of cells, bounds included.
+struct Area {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+}
+
+impl Area {
+    fn all(cells: &Grid<Cell>) -> Self {
+        Area { left: 0, top: 0, right: cells.width() - 1, bottom: cells.height() - 1 }
+    }
+
+    /// The smallest area holding every cell whose colour differs between both
+    /// frames, `None` when they look the same.
+    fn changed(before: &Grid<Cell>, after: &Grid<Cell>) -> Option<Self> {
+        before.iter()
+              .zip(after.iter())
+              .filter(|((_, before), (_, after))| before.style != after.style)
+              .map(|((point, _), _)| point)
+              .fold(None, |area: Option<Area>, point| match area {
+                  None => Some(Area { left: point.x, top: point.y, right: point.x, bottom: point.y }),
+                  Some(area) => Some(Area {
+                      left: area.left.min(point.x),
+                      top: area.top.min(point.y),
+                      right: area.right.max(point.x),
+                      bottom: area.bottom.max(point.y),
+                  }),
+              })
+    }
+}
+
+fn frame(cells: &Grid<Cell>, scale: u32, area: Area, delay: u16) -> gif::Frame<'static> {
+    let scale_u16 = scale as u16;
+    let mut frame = gif::Frame::from_indexed_pixels((area.right - area.left + 1) as u16 * scale_u16,
+                                                    (area.bottom - area.top + 1) as u16 * scale_u16,
+                                                    pixels(cells, scale, &area),
+                                                    None);
+    frame.left = area.left as u16 * scale_u16;
+    frame.top = area.top as u16 * scale_u16;
+    frame.delay = delay;
+    frame
+}
+
+/// The palette indices of the pixels of `area`, row by row.
+fn pixels(cells: &Grid<Cell>, scale: u32, area: &Area) -> Vec<u8> {
+    let scale = scale as usize;
+    let mut pixels = Vec::new();
+    for y in area.top..=area.bottom {
+        let row: Vec<u8> = (area.left..=area.right)
+            .flat_map(|x| {
+                let index = palette_index(cells.get(&Point::new(x, y)).unwrap().style);
+                std::iter::repeat_n(index, scale)
+            })
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&row);
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use recording::Recorder;
+
+    #[test]
+    fn images_have_a_square_of_pixels_per_cell() {
+        let mut cells = Grid::filled(3, 2, Cell::FLOOR);
+        cells.set(&Point::new(2, 1), Cell::new('@', Style::Robot));
+
+        let mut png = Vec::new();
+        write_png(&cells, 4, &mut png).unwrap();
+        let decoder = png::Decoder::new(io::Cursor::new(png));
+        let info = decoder.read_info().unwrap().info().clone();
+        assert_eq!((info.width, info.height), (12, 8));
+
+        let mut recorder = Recorder::new("moves");
+        recorder.record(cells.clone(), "start");
+        recorder.record(cells.clone(), "nothing happens");
+        cells.set(&Point::new(0, 0), Cell::new('@', Style::Robot));
+        recorder.record(cells, "a robot shows up");
+        let mut gif = Vec::new();
+        write_gif(&recorder.finish(), &[0, 1, 2], 4, 10.0, &mut gif).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new().read_info(io::Cursor::new(gif)).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (12, 8));
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!((first.width, first.height, first.delay), (12, 8, 20));
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!((second.left, second.top, second.width, second.height), (0, 0, 4, 4));
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+}
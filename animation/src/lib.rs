@@ -0,0 +1,7 @@
+//! Playing recordings in the terminal and exporting them as images.
+
+mod export;
+mod player;
+
+pub use export::{write_gif, write_png};
+pub use player::{play, print, PlayOptions};
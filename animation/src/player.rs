@@ -0,0 +1,215 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use grid::{Grid, Point};
+use recording::{Cell, Recording, Style};
+
+const HELP: &str = "space pause  \u{2190}\u{2192} step  PgUp/PgDn jump  Home/End  +/- speed  q quit";
+
+/// How [`play`] starts out.
+#[derive(Debug, Clone, Copy)]
+pub struct PlayOptions {
+    pub frames_per_second: f64,
+    /// The frame to start at.
+    pub start: usize,
+    pub paused: bool,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        PlayOptions { frames_per_second: 20.0, start: 0, paused: false }
+    }
+}
+
+/// Plays a recording in the terminal until it is quit. It can be paused,
+/// stepped through a frame at a time in both directions, and sought to any
+/// frame. The grid is cut off where the terminal ends.
+pub fn play(recording: &Recording, options: PlayOptions) -> io::Result<()> {
+    if recording.is_empty() {
+        return Ok(());
+    }
+    let mut out = io::stdout();
+    let _screen = Screen::enter(&mut out)?;
+
+    let last = recording.len() - 1;
+    let mut index = options.start.min(last);
+    let mut cells = recording.frame(index);
+    let mut paused = options.paused;
+    let mut frames_per_second = options.frames_per_second.clamp(0.25, 1000.0);
+    let mut next_frame = Instant::now();
+
+    loop {
+        draw(&mut out, recording, index, &cells, paused, frames_per_second)?;
+
+        let timeout = if paused {
+            Duration::from_secs(60)
+        } else {
+            next_frame.saturating_duration_since(Instant::now())
+        };
+        if !event::poll(timeout)? {
+            if !paused {
+                if index < last {
+                    index += 1;
+                    recording.advance(index, &mut cells);
+                } else {
+                    paused = true;
+                }
+                next_frame = Instant::now() + Duration::from_secs_f64(1.0 / frames_per_second);
+            }
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            // Redraws on the next turn, which handles resizing as well
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        let jump = (recording.len() / 10).max(1);
+        let target = match key {
+            KeyEvent { code: KeyCode::Char('q') | KeyCode::Esc, .. } => return Ok(()),
+            KeyEvent { code: KeyCode::Char('c'), modifiers, .. } if modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyEvent { code: KeyCode::Char(' '), .. } => {
+                paused = !paused;
+                if !paused && index == last {
+                    0
+                } else {
+                    index
+                }
+            }
+            KeyEvent { code: KeyCode::Right | KeyCode::Char('l'), .. } => {
+                paused = true;
+                (index + 1).min(last)
+            }
+            KeyEvent { code: KeyCode::Left | KeyCode::Char('h'), .. } => {
+                paused = true;
+                index.saturating_sub(1)
+            }
+            KeyEvent { code: KeyCode::PageDown, .. } => (index + jump).min(last),
+            KeyEvent { code: KeyCode::PageUp, .. } => index.saturating_sub(jump),
+            KeyEvent { code: KeyCode::Home, .. } => 0,
+            KeyEvent { code: KeyCode::End, .. } => last,
+            KeyEvent { code: KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up, .. } => {
+                frames_per_second = (frames_per_second * 2.0).min(1000.0);
+                index
+            }
+            KeyEvent { code: KeyCode::Char('-') | KeyCode::Down, .. } => {
+                frames_per_second = (frames_per_second / 2.0).max(0.25);
+                index
+            }
+            _ => index,
+        };
+
+        if target == index + 1 {
+            recording.advance(target, &mut cells);
+        } else if target != index {
+            cells = recording.frame(target);
+        }
+        index = target;
+        next_frame = Instant::now() + Duration::from_secs_f64(1.0 / frames_per_second);
+    }
+}
+
+/// Writes every frame of a recording as plain text, each preceded by its
+/// caption. This is what gets shown when the output is not a terminal.
+pub fn print(recording: &Recording, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "{}", recording.title())?;
+    let mut cells = Grid::filled(0, 0, Cell::FLOOR);
+    for index in 0..recording.len() {
+        if index == 0 {
+            cells = recording.frame(0);
+        } else {
+            recording.advance(index, &mut cells);
+        }
+        writeln!(out)?;
+        writeln!(out, "{}", recording.caption(index))?;
+        write!(out, "{}", cells.map(|cell| cell.symbol))?;
+    }
+    Ok(())
+}
+
+fn colour(style: Style) -> Color {
+    match style {
+        Style::Floor => Color::DarkGrey,
+        Style::Wall => Color::Grey,
+        Style::Box => Color::Yellow,
+        Style::Robot => Color::Green,
+        Style::Visited => Color::Cyan,
+        Style::Marker => Color::Magenta,
+        Style::Region(_) => {
+            let [r, g, b] = style.rgb();
+            Color::Rgb { r, g, b }
+        }
+    }
+}
+
+fn draw(out: &mut impl Write,
+        recording: &Recording,
+        index: usize,
+        cells: &Grid<Cell>,
+        paused: bool,
+        frames_per_second: f64) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let width = cells.width().min(columns as i32);
+    // One line for the caption above the grid and one for the help below it
+    let height = cells.height().min(rows.saturating_sub(2) as i32);
+
+    let state = if paused { "paused" } else { "playing" };
+    let status = format!("{} | frame {}/{} | {} | {} fps, {}",
+                         recording.title(), index + 1, recording.len(), recording.caption(index), frames_per_second, state);
+    queue!(out,
+           cursor::MoveTo(0, 0),
+           SetAttribute(Attribute::Bold),
+           Print(clip(&status, columns)),
+           SetAttribute(Attribute::Reset),
+           Clear(ClearType::UntilNewLine))?;
+
+    for y in 0..height {
+        queue!(out, cursor::MoveTo(0, y as u16 + 1))?;
+        // Only switches colours where the style changes along the row
+        let mut current: Option<Style> = None;
+        for x in 0..width {
+            let cell = cells.get(&Point::new(x, y)).unwrap();
+            if current != Some(cell.style) {
+                queue!(out, SetForegroundColor(colour(cell.style)))?;
+                current = Some(cell.style);
+            }
+            queue!(out, Print(cell.symbol))?;
+        }
+        queue!(out, ResetColor, Clear(ClearType::UntilNewLine))?;
+    }
+
+    queue!(out,
+           cursor::MoveTo(0, height as u16 + 1),
+           Clear(ClearType::FromCursorDown),
+           SetForegroundColor(Color::DarkGrey),
+           Print(clip(HELP, columns)),
+           ResetColor)?;
+    out.flush()
+}
+
+fn clip(text: &str, columns: u16) -> String {
+    text.chars().take(columns as usize).collect()
+}
+
+/// The alternate screen in raw mode, restored on drop even when playing fails.
+struct Screen;
+
+impl Screen {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, cursor::Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
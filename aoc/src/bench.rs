@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use common::{Params, Runner};
+
+/// The timings of one stage (`parse`, `part1` or `part2`) of a solution over
+/// all iterations, which is also what a baseline file stores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measurement {
+    pub name: String,
+    pub stage: String,
+    pub iterations: usize,
+    pub median_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+impl Measurement {
+    fn new(name: &str, stage: &str, durations: &[Duration]) -> Self {
+        let mut ms: Vec<f64> = durations.iter().map(|duration| duration.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(|a, b| a.total_cmp(b));
+        let middle = ms.len() / 2;
+        let median_ms = if ms.len().is_multiple_of(2) { (ms[middle - 1] + ms[middle]) / 2.0 } else { ms[middle] };
+
+        Measurement {
+            name: name.to_string(),
+            stage: stage.to_string(),
+            iterations: ms.len(),
+            median_ms,
+            min_ms: ms[0],
+            max_ms: ms[ms.len() - 1],
+        }
+    }
+
+    /// Half the distance between the fastest and slowest run, relative to the
+    /// median.
+    fn spread(&self) -> f64 {
+        if self.median_ms == 0.0 { 0.0 } else { (self.max_ms - self.min_ms) / 2.0 / self.median_ms * 100.0 }
+    }
+}
+
+/// Solves `parts` of `filename` `iterations` times, timing the parsing and
+/// every part separately.
+pub fn bench(solution: &dyn Runner,
+             filename: &str,
+             parts: &[u8],
+             params: &Params,
+             iterations: usize) -> Result<Vec<Measurement>, Box<dyn Error>> {
+    let mut parse: Vec<Duration> = Vec::new();
+    let mut solve: Vec<Vec<Duration>> = vec![Vec::new(); parts.len()];
+
+    for _ in 0..iterations {
+        let run = solution.run_timed(filename, parts, params)?;
+        parse.push(run.parse);
+        for (index, (_, _, elapsed)) in run.parts.into_iter().enumerate() {
+            solve[index].push(elapsed);
+        }
+    }
+
+    let mut measurements = vec![Measurement::new(solution.name(), "parse", &parse)];
+    for (part, durations) in parts.iter().zip(solve) {
+        measurements.push(Measurement::new(solution.name(), &format!("part{}", part), &durations));
+    }
+    Ok(measurements)
+}
+
+pub fn read_baseline(filename: &Path) -> Result<Vec<Measurement>, Box<dyn Error>> {
+    let content = fs::read_to_string(filename)
+        .map_err(|error| format!("{}: {}", filename.display(), error))?;
+    Ok(serde_json::from_str(&content).map_err(|error| format!("{}: {}", filename.display(), error))?)
+}
+
+pub fn save_baseline(filename: &Path, measurements: &[Measurement]) -> Result<(), Box<dyn Error>> {
+    fs::write(filename, serde_json::to_string_pretty(measurements)? + "\n")
+        .map_err(|error| format!("{}: {}", filename.display(), error))?;
+    Ok(())
+}
+
+/// Prints the measurements, comparing them with `baseline` when given. A
+/// median more than `threshold` percent slower than the baseline counts as a
+/// regression. Returns whether there were none.
+pub fn print_table(measurements: &[Measurement], baseline: Option<&[Measurement]>, threshold: f64) -> bool {
+    let name_width = measurements.iter().map(|m| m.name.len()).max().unwrap_or(0).max("solution".len());
+    let mut regressions = 0;
+
+    print!("{:<name_width$}  stage  {:>12}  {:>7}  {:>12}  {:>12}", "solution", "median", "spread", "min", "max");
+    if baseline.is_some() {
+        print!("  {:>12}  change", "baseline");
+    }
+    println!();
+
+    for measurement in measurements {
+        print!("{:<name_width$}  {:<5}  {:>9.3} ms  {:>6.1}%  {:>9.3} ms  {:>9.3} ms",
+               measurement.name,
+               measurement.stage,
+               measurement.median_ms,
+               measurement.spread(),
+               measurement.min_ms,
+               measurement.max_ms);
+
+        if let Some(baseline) = baseline {
+            match baseline.iter().find(|b| b.name == measurement.name && b.stage == measurement.stage) {
+                Some(before) => {
+                    let change = if before.median_ms == 0.0 {
+                        0.0
+                    } else {
+                        (measurement.median_ms - before.median_ms) / before.median_ms * 100.0
+                    };
+                    let verdict = if change > threshold {
+                        regressions += 1;
+                        "  REGRESSION"
+                    } else if change < -threshold {
+                        "  faster"
+                    } else {
+                        ""
+                    };
+                    print!("  {:>9.3} ms  {:+.1}%{}", before.median_ms, change, verdict);
+                }
+                None => print!("  {:>12}  new", "-"),
+            }
+        }
+        println!();
+    }
+
+    if baseline.is_some() {
+        println!();
+        println!("{} regression(s) above {}%", regressions, threshold);
+    }
+    regressions == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_an_even_number_of_runs_is_the_mean_of_the_middle_two() {
+        let durations: Vec<Duration> = [4, 1, 3, 2].iter().map(|ms| Duration::from_millis(*ms)).collect();
+        let measurement = Measurement::new("day-01", "part1", &durations);
+        assert_eq!(measurement.median_ms, 2.5);
+        assert_eq!((measurement.min_ms, measurement.max_ms), (1.0, 4.0));
+        assert_eq!(measurement.spread(), 60.0);
+    }
+}
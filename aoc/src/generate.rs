@@ -0,0 +1,638 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+/// The knobs of a generated input.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// How big the input gets, see [`Generator::size`] for what it counts.
+    pub size: usize,
+    /// The number of output wire pairs swapped in the day 24 circuit.
+    pub swaps: usize,
+}
+
+/// Writes random inputs in the format of one day's puzzle input.
+pub struct Generator {
+    pub day: u8,
+    /// What `--size` counts for this day.
+    pub size: &'static str,
+    /// A size close to the real puzzle input.
+    pub default_size: usize,
+    generate: fn(&mut StdRng, &Options) -> String,
+    /// The parameters of the day that a generated input needs set, because
+    /// they depend on the options.
+    params: fn(&Options) -> Vec<(&'static str, usize)>,
+}
+
+impl Generator {
+    /// The input for `seed`, the same seed and options always give the same
+    /// input.
+    pub fn generate(&self, seed: u64, options: &Options) -> String {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (self.generate)(&mut rng, options)
+    }
+
+    /// The values of the day's parameters that the input for `options`
+    /// needs, as `name`, `value` pairs.
+    pub fn params(&self, options: &Options) -> Vec<(&'static str, usize)> {
+        (self.params)(options)
+    }
+}
+
+pub const GENERATORS: [Generator; 25] = [
+    Generator { day: 1, size: "pairs of location ids", default_size: 1000, generate: day_01, params: no_params },
+    Generator { day: 2, size: "reports", default_size: 1000, generate: day_02, params: no_params },
+    Generator { day: 3, size: "instructions", default_size: 700, generate: day_03, params: no_params },
+    Generator { day: 4, size: "rows and columns", default_size: 140, generate: day_04, params: no_params },
+    Generator { day: 5, size: "updates", default_size: 200, generate: day_05, params: no_params },
+    Generator { day: 6, size: "rows and columns", default_size: 130, generate: day_06, params: no_params },
+    Generator { day: 7, size: "equations", default_size: 850, generate: day_07, params: no_params },
+    Generator { day: 8, size: "rows and columns", default_size: 50, generate: day_08, params: no_params },
+    Generator { day: 9, size: "digits of the disk map", default_size: 19999, generate: day_09, params: no_params },
+    Generator { day: 10, size: "rows and columns", default_size: 50, generate: day_10, params: no_params },
+    Generator { day: 11, size: "stones", default_size: 8, generate: day_11, params: no_params },
+    Generator { day: 12, size: "rows and columns", default_size: 140, generate: day_12, params: no_params },
+    Generator { day: 13, size: "claw machines", default_size: 320, generate: day_13, params: no_params },
+    Generator { day: 14, size: "robots", default_size: 500, generate: day_14, params: no_params },
+    Generator { day: 15, size: "rows and columns", default_size: 50, generate: day_15, params: no_params },
+    Generator { day: 16, size: "rows and columns", default_size: 141, generate: day_16, params: no_params },
+    Generator { day: 17, size: "octal digits of register A", default_size: 8, generate: day_17, params: no_params },
+    Generator { day: 18, size: "largest coordinate of the memory space", default_size: 70, generate: day_18, params: day_18_params },
+    Generator { day: 19, size: "designs", default_size: 400, generate: day_19, params: no_params },
+    Generator { day: 20, size: "rows and columns", default_size: 141, generate: day_20, params: no_params },
+    Generator { day: 21, size: "door codes", default_size: 5, generate: day_21, params: no_params },
+    Generator { day: 22, size: "buyers", default_size: 2000, generate: day_22, params: no_params },
+    Generator { day: 23, size: "computers", default_size: 520, generate: day_23, params: no_params },
+    Generator { day: 24, size: "bits of x and y", default_size: 45, generate: day_24, params: no_params },
+    Generator { day: 25, size: "locks and keys", default_size: 500, generate: day_25, params: no_params },
+];
+
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+/// For the days whose generated inputs run with the default parameters.
+fn no_params(_: &Options) -> Vec<(&'static str, usize)> {
+    Vec::new()
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn grid(rows: &[Vec<char>]) -> String {
+    lines(rows.iter().map(|row| row.iter().collect()))
+}
+
+fn day_01(rng: &mut StdRng, options: &Options) -> String {
+    let left: Vec<usize> = (0..options.size).map(|_| rng.random_range(10000..100000)).collect();
+    // Reuse some of the left ids on the right, otherwise the similarity score is always 0
+    let right: Vec<usize> = (0..options.size).map(|_| if rng.random_bool(0.3) {
+                                                     *left.choose(rng).unwrap()
+                                                 } else {
+                                                     rng.random_range(10000..100000)
+                                                 })
+                                             .collect();
+    lines(left.iter().zip(right).map(|(l, r)| format!("{}   {}", l, r)))
+}
+
+fn day_02(rng: &mut StdRng, options: &Options) -> String {
+    lines((0..options.size).map(|_| {
+        let length = rng.random_range(5..=8);
+        let direction: i64 = if rng.random_bool(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.random_range(50..=70)];
+        for _ in 1..length {
+            let step = rng.random_range(1..=3) * direction;
+            levels.push(levels.last().unwrap() + step);
+        }
+        // Break about half of the reports, some of them beyond repair
+        for _ in 0..[0, 0, 1, 1, 2].choose(rng).copied().unwrap() {
+            let index = rng.random_range(0..length);
+            levels[index] = (levels[index] + rng.random_range(-6..=6)).max(1);
+        }
+        levels.iter().map(|level| level.to_string()).collect::<Vec<_>>().join(" ")
+    }))
+}
+
+fn day_03(rng: &mut StdRng, options: &Options) -> String {
+    const JUNK: [&str; 16] = ["!", "@", "#", "$", "%", "^", "&", "*", "[", "]", "{", "}", "<", ">", "what()", "from()"];
+    const BROKEN: [&str; 4] = ["mul(4*", "mul ( 2 , 4 )", "mul[3,7]", "mul(6,9!"];
+    let mut text = String::new();
+    for instruction in 0..options.size {
+        if instruction > 0 && instruction % 100 == 0 {
+            text.push('\n');
+        }
+        for _ in 0..rng.random_range(0..4) {
+            text.push_str(JUNK.choose(rng).unwrap());
+        }
+        match rng.random_range(0..10) {
+            0 => text.push_str("do()"),
+            1 => text.push_str("don't()"),
+            2 => text.push_str(BROKEN.choose(rng).unwrap()),
+            _ => text.push_str(&format!("mul({},{})", rng.random_range(1..1000), rng.random_range(1..1000))),
+        }
+    }
+    text + "\n"
+}
+
+fn day_04(rng: &mut StdRng, options: &Options) -> String {
+    let rows: Vec<Vec<char>> = (0..options.size).map(|_| (0..options.size).map(|_| *['X', 'M', 'A', 'S'].choose(rng).unwrap())
+                                                                         .collect())
+                                                .collect();
+    grid(&rows)
+}
+
+fn day_05(rng: &mut StdRng, options: &Options) -> String {
+    // Every pair of pages has a rule, ordered by a hidden permutation, so every update has exactly one valid order
+    let mut pages: Vec<usize> = (10..100).collect();
+    pages.shuffle(rng);
+    pages.truncate(49);
+
+    let mut rules: Vec<String> = Vec::new();
+    for (index, before) in pages.iter().enumerate() {
+        for after in &pages[index + 1..] {
+            rules.push(format!("{}|{}", before, after));
+        }
+    }
+    rules.shuffle(rng);
+
+    let updates = (0..options.size).map(|_| {
+        let length = rng.random_range(2..=11) * 2 + 1;
+        let mut update: Vec<usize> = pages.choose_multiple(rng, length).copied().collect();
+        if rng.random_bool(0.5) {
+            update.sort_by_key(|page| pages.iter().position(|p| p == page));
+        }
+        update.iter().map(|page| page.to_string()).collect::<Vec<_>>().join(",")
+    });
+
+    lines(rules) + "\n" + &lines(updates)
+}
+
+fn day_06(rng: &mut StdRng, options: &Options) -> String {
+    let size = options.size.max(2) as i64;
+    // Part 1 assumes the guard leaves the map, so draw maps until one lets the guard go
+    loop {
+        let mut rows: Vec<Vec<char>> = (0..size).map(|_| (0..size).map(|_| if rng.random_bool(0.08) { '#' } else { '.' })
+                                                                  .collect())
+                                                .collect();
+        let (x, y) = (rng.random_range(0..size), rng.random_range(0..size));
+        rows[y as usize][x as usize] = '^';
+
+        let at = |x: i64, y: i64| -> Option<char> {
+            (0..size).contains(&x).then_some(())?;
+            (0..size).contains(&y).then_some(())?;
+            Some(rows[y as usize][x as usize])
+        };
+        let mut seen: HashSet<(i64, i64, i64, i64)> = HashSet::new();
+        let (mut x, mut y, mut dx, mut dy) = (x, y, 0, -1);
+        let leaves = loop {
+            if !seen.insert((x, y, dx, dy)) {
+                break false;
+            }
+            match at(x + dx, y + dy) {
+                None => break true,
+                Some('#') => (dx, dy) = (-dy, dx),
+                Some(_) => (x, y) = (x + dx, y + dy),
+            }
+        };
+        if leaves {
+            return grid(&rows);
+        }
+    }
+}
+
+fn day_07(rng: &mut StdRng, options: &Options) -> String {
+    lines((0..options.size).map(|_| {
+        let numbers: Vec<i64> = (0..rng.random_range(2..=9)).map(|_| rng.random_range(1..100)).collect();
+        let mut value = numbers[0];
+        for number in &numbers[1..] {
+            value = match rng.random_range(0..3) {
+                0 => value + number,
+                1 => value * number,
+                _ => format!("{}{}", value, number).parse().unwrap(),
+            };
+        }
+        // Some equations cannot be made true
+        if rng.random_bool(0.3) {
+            value += 1;
+        }
+        format!("{}: {}", value, numbers.iter().map(|number| number.to_string()).collect::<Vec<_>>().join(" "))
+    }))
+}
+
+fn day_08(rng: &mut StdRng, options: &Options) -> String {
+    let frequencies: Vec<char> = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+    let mut rows = vec![vec!['.'; options.size]; options.size];
+    for _ in 0..options.size * options.size / 30 {
+        let (x, y) = (rng.random_range(0..options.size), rng.random_range(0..options.size));
+        rows[y][x] = *frequencies.choose(rng).unwrap();
+    }
+    grid(&rows)
+}
+
+fn day_09(rng: &mut StdRng, options: &Options) -> String {
+    // Odd length, so the map starts and ends with a file
+    let length = options.size.max(1) | 1;
+    let map: String = (0..length).map(|index| {
+        let digit = if index % 2 == 0 { rng.random_range(1..=9) } else { rng.random_range(0..=9) };
+        char::from_digit(digit, 10).unwrap()
+    }).collect();
+    map + "\n"
+}
+
+fn day_10(rng: &mut StdRng, options: &Options) -> String {
+    // Mostly follow the neighbour on the left, so there are trails to find
+    let mut rows: Vec<Vec<u32>> = Vec::new();
+    for _ in 0..options.size {
+        let mut row: Vec<u32> = vec![rng.random_range(0..10)];
+        for _ in 1..options.size {
+            let height = match (row.last().copied().unwrap(), rng.random_range(0..5)) {
+                (left, 0..=1) if left < 9 => left + 1,
+                (left, 2) if left > 0 => left - 1,
+                _ => rng.random_range(0..10),
+            };
+            row.push(height);
+        }
+        rows.push(row);
+    }
+    lines(rows.iter().map(|row| row.iter().map(|height| char::from_digit(*height, 10).unwrap()).collect()))
+}
+
+fn day_11(rng: &mut StdRng, options: &Options) -> String {
+    let stones: Vec<String> = (0..options.size).map(|_| rng.random_range(0..1_000_000).to_string()).collect();
+    stones.join(" ") + "\n"
+}
+
+fn day_12(rng: &mut StdRng, options: &Options) -> String {
+    // Copy a neighbour most of the time, so plants grow into regions
+    let mut rows: Vec<Vec<char>> = Vec::new();
+    for y in 0..options.size {
+        let mut row: Vec<char> = Vec::new();
+        for x in 0..options.size {
+            let plant = match rng.random_range(0..10) {
+                0..=3 if x > 0 => row[x - 1],
+                4..=7 if y > 0 => rows[y - 1][x],
+                _ => rng.random_range('A'..='Z'),
+            };
+            row.push(plant);
+        }
+        rows.push(row);
+    }
+    grid(&rows)
+}
+
+fn day_13(rng: &mut StdRng, options: &Options) -> String {
+    let machines = (0..options.size).map(|_| {
+        let (a, b) = loop {
+            let a: (i64, i64) = (rng.random_range(10..100), rng.random_range(10..100));
+            let b: (i64, i64) = (rng.random_range(10..100), rng.random_range(10..100));
+            if a.0 * b.1 != a.1 * b.0 {
+                break (a, b);
+            }
+        };
+        let (presses_a, presses_b) = (rng.random_range(0..=100), rng.random_range(0..=100));
+        let mut prize = (a.0 * presses_a + b.0 * presses_b, a.1 * presses_a + b.1 * presses_b);
+        // Most machines cannot be won
+        if rng.random_bool(0.6) {
+            prize.0 += rng.random_range(1..50);
+        }
+        format!("Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n", a.0, a.1, b.0, b.1, prize.0, prize.1)
+    });
+    machines.collect::<Vec<_>>().join("\n")
+}
+
+fn day_14(rng: &mut StdRng, options: &Options) -> String {
+    lines((0..options.size).map(|_| format!("p={},{} v={},{}",
+                                            rng.random_range(0..101),
+                                            rng.random_range(0..103),
+                                            rng.random_range(-99..100),
+                                            rng.random_range(-99..100))))
+}
+
+fn day_15(rng: &mut StdRng, options: &Options) -> String {
+    let size = options.size.max(3);
+    let mut rows: Vec<Vec<char>> = (0..size).map(|y| (0..size).map(|x| {
+        if x == 0 || y == 0 || x == size - 1 || y == size - 1 {
+            '#'
+        } else {
+            match rng.random_range(0..20) {
+                0 => '#',
+                1..=5 => 'O',
+                _ => '.',
+            }
+        }
+    }).collect()).collect();
+    rows[rng.random_range(1..size - 1)][rng.random_range(1..size - 1)] = '@';
+
+    let moves: Vec<char> = (0..size * size * 2).map(|_| *['<', '>', '^', 'v'].choose(rng).unwrap()).collect();
+    grid(&rows) + "\n" + &lines(moves.chunks(70).map(|chunk| chunk.iter().collect()))
+}
+
+/// A maze of corridors one cell wide, with the start in the bottom left and
+/// the end in the top right corner. Knocking out `extra_walls` walls adds
+/// loops to it.
+fn maze(rng: &mut StdRng, size: usize, extra_walls: usize) -> Vec<Vec<char>> {
+    // Corridors run through the odd coordinates, so the size has to be odd as well
+    let size = size.max(5) | 1;
+    let mut rows = vec![vec!['#'; size]; size];
+    let mut stack = vec![(1, size - 2)];
+    rows[size - 2][1] = '.';
+    while let Some(&(x, y)) = stack.last() {
+        let mut next: Vec<(usize, usize)> = [(0, 2), (2, 0), (0, -2), (-2, 0)].iter()
+            .map(|(dx, dy)| ((x as i64 + dx) as usize, (y as i64 + dy) as usize))
+            .filter(|&(nx, ny)| nx > 0 && ny > 0 && nx < size - 1 && ny < size - 1 && rows[ny][nx] == '#')
+            .collect();
+        next.shuffle(rng);
+        match next.first() {
+            Some(&(nx, ny)) => {
+                rows[(y + ny) / 2][(x + nx) / 2] = '.';
+                rows[ny][nx] = '.';
+                stack.push((nx, ny));
+            }
+            None => { stack.pop(); }
+        }
+    }
+    for _ in 0..extra_walls {
+        let (x, y) = (rng.random_range(1..size - 1), rng.random_range(1..size - 1));
+        if (x + y) % 2 == 1 {
+            rows[y][x] = '.';
+        }
+    }
+    rows[size - 2][1] = 'S';
+    rows[1][size - 2] = 'E';
+    rows
+}
+
+fn day_16(rng: &mut StdRng, options: &Options) -> String {
+    grid(&maze(rng, options.size, options.size * options.size / 50))
+}
+
+fn day_17(rng: &mut StdRng, options: &Options) -> String {
+    // The shape of the real programs: every round outputs a function of the lowest bits of A, then shifts them out
+    let a = rng.random_range(1..8_u64.pow(options.size.clamp(1, 21) as u32));
+    let mut body = vec![[0, 3], [4, rng.random_range(0..8)], [1, rng.random_range(0..8)]];
+    body.shuffle(rng);
+    let program: Vec<String> = [[2, 4], [1, rng.random_range(0..8)], [7, 5]].into_iter()
+                                                                            .chain(body)
+                                                                            .chain([[5, 5], [3, 0]])
+                                                                            .flatten()
+                                                                            .map(|n: u64| n.to_string())
+                                                                            .collect();
+    format!("Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n", a, program.join(","))
+}
+
+/// The largest coordinate of the day 18 memory space, and how many bytes fall
+/// before the exit may be cut off.
+fn day_18_space(options: &Options) -> (usize, usize) {
+    let size = options.size.max(1);
+    // Like the real input, the exit stays reachable while the first 1024 of 71 by 71 bytes fall
+    let cells = (size + 1) * (size + 1) - 2;
+    (size, cells * 1024 / 5041)
+}
+
+fn day_18_params(options: &Options) -> Vec<(&'static str, usize)> {
+    let (size, falling) = day_18_space(options);
+    vec![("size", size), ("bytes", falling)]
+}
+
+fn day_18(rng: &mut StdRng, options: &Options) -> String {
+    let (size, falling) = day_18_space(options);
+    let mut bytes: Vec<(usize, usize)> = (0..=size).flat_map(|y| (0..=size).map(move |x| (x, y)))
+                                                   .filter(|&cell| cell != (0, 0) && cell != (size, size))
+                                                   .collect();
+    loop {
+        bytes.shuffle(rng);
+        let corrupted: HashSet<&(usize, usize)> = bytes.iter().take(falling).collect();
+        let mut seen: HashSet<(usize, usize)> = HashSet::from([(0, 0)]);
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::from([(0, 0)]);
+        while let Some((x, y)) = queue.pop_front() {
+            let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+            for next in neighbours {
+                if next.0 <= size && next.1 <= size && !corrupted.contains(&next) && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if seen.contains(&(size, size)) {
+            return lines(bytes.iter().map(|(x, y)| format!("{},{}", x, y)));
+        }
+    }
+}
+
+fn day_19(rng: &mut StdRng, options: &Options) -> String {
+    const COLOURS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+    let mut towels: BTreeSet<String> = BTreeSet::new();
+    while towels.len() < 40 {
+        let length = [1, 2, 2, 3, 3, 3, 4, 5, 6, 8].choose(rng).copied().unwrap();
+        towels.insert((0..length).map(|_| *COLOURS.choose(rng).unwrap()).collect());
+    }
+    let towels: Vec<String> = towels.into_iter().collect();
+
+    let designs = (0..options.size).map(|_| {
+        let mut design: Vec<char> = (0..rng.random_range(2..=10)).flat_map(|_| towels.choose(rng).unwrap().chars())
+                                                                 .collect();
+        // Some designs become impossible
+        if rng.random_bool(0.3) {
+            let index = rng.random_range(0..design.len());
+            design[index] = *COLOURS.choose(rng).unwrap();
+        }
+        design.into_iter().collect()
+    });
+    towels.join(", ") + "\n\n" + &lines(designs)
+}
+
+fn day_20(rng: &mut StdRng, options: &Options) -> String {
+    // A racetrack is a single path, so keep only the way from the start to the end of a maze
+    let mut rows = maze(rng, options.size, 0);
+    let size = rows.len();
+    let start = (1, size - 2);
+    let mut previous: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        for (nx, ny) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
+            if rows[ny][nx] != '#' && (nx, ny) != start && !previous.contains_key(&(nx, ny)) {
+                previous.insert((nx, ny), (x, y));
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    let mut track: HashSet<(usize, usize)> = HashSet::from([start]);
+    let mut cell = (size - 2, 1);
+    while cell != start {
+        track.insert(cell);
+        cell = previous[&cell];
+    }
+    for (y, row) in rows.iter_mut().enumerate() {
+        for (x, c) in row.iter_mut().enumerate() {
+            if *c == '.' && !track.contains(&(x, y)) {
+                *c = '#';
+            }
+        }
+    }
+    grid(&rows)
+}
+
+fn day_21(rng: &mut StdRng, options: &Options) -> String {
+    lines((0..options.size).map(|_| format!("{:03}A", rng.random_range(0..1000))))
+}
+
+fn day_22(rng: &mut StdRng, options: &Options) -> String {
+    lines((0..options.size).map(|_| rng.random_range(1..16_777_216).to_string()))
+}
+
+fn day_23(rng: &mut StdRng, options: &Options) -> String {
+    let mut names: Vec<String> = ('a'..='z').flat_map(|a| ('a'..='z').map(move |b| format!("{}{}", a, b)))
+                                           .collect();
+    names.shuffle(rng);
+    names.truncate(options.size.clamp(2, names.len()));
+
+    let mut connections: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut connect = |a: usize, b: usize| if a != b { connections.insert((a.min(b), a.max(b))); };
+    for a in 0..names.len() {
+        for _ in 0..4 {
+            connect(a, rng.random_range(0..names.len()));
+        }
+    }
+    // Plant a LAN party for part 2
+    let party: Vec<usize> = (0..names.len()).collect::<Vec<_>>().choose_multiple(rng, 13.min(names.len())).copied().collect();
+    for a in &party {
+        for b in &party {
+            connect(*a, *b);
+        }
+    }
+
+    let mut connections: Vec<String> = connections.iter()
+                                                  .map(|(a, b)| if rng.random_bool(0.5) {
+                                                      format!("{}-{}", names[*a], names[*b])
+                                                  } else {
+                                                      format!("{}-{}", names[*b], names[*a])
+                                                  })
+                                                  .collect();
+    connections.shuffle(rng);
+    lines(connections)
+}
+
+fn day_24(rng: &mut StdRng, options: &Options) -> String {
+    let bits = options.size.clamp(2, 60);
+    let mut used: HashSet<String> = HashSet::new();
+    let mut wire = |rng: &mut StdRng| loop {
+        let name: String = (0..3).map(|index| if index == 0 { rng.random_range('a'..='w') } else { rng.random_range('a'..='z') })
+                                 .collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    // A ripple carry adder: z = x XOR y XOR carry, carry = (x AND y) OR ((x XOR y) AND carry)
+    let mut gates: Vec<(String, &str, String, String)> = Vec::new();
+    let mut carry = wire(rng);
+    gates.push(("x00".to_string(), "XOR", "y00".to_string(), "z00".to_string()));
+    gates.push(("x00".to_string(), "AND", "y00".to_string(), carry.clone()));
+    for bit in 1..bits {
+        let (x, y, z) = (format!("x{:02}", bit), format!("y{:02}", bit), format!("z{:02}", bit));
+        let (sum, both, propagated) = (wire(rng), wire(rng), wire(rng));
+        let next_carry = if bit == bits - 1 { format!("z{:02}", bits) } else { wire(rng) };
+        gates.push((x.clone(), "XOR", y.clone(), sum.clone()));
+        gates.push((sum.clone(), "XOR", carry.clone(), z));
+        gates.push((x, "AND", y, both.clone()));
+        gates.push((sum, "AND", carry, propagated.clone()));
+        gates.push((both, "OR", propagated, next_carry.clone()));
+        carry = next_carry;
+    }
+
+    // Swap outputs, as long as the circuit stays free of cycles
+    let swaps = options.swaps.min(gates.len() / 2);
+    let mut swapped = loop {
+        let mut swapped = gates.clone();
+        let indices: Vec<usize> = (0..gates.len()).collect::<Vec<_>>().choose_multiple(rng, swaps * 2).copied().collect();
+        for pair in indices.chunks(2) {
+            let (a, b) = (swapped[pair[0]].3.clone(), swapped[pair[1]].3.clone());
+            swapped[pair[0]].3 = b;
+            swapped[pair[1]].3 = a;
+        }
+        if is_acyclic(&swapped) {
+            break swapped;
+        }
+    };
+    swapped.shuffle(rng);
+
+    let inputs = ['x', 'y'].into_iter()
+                           .flat_map(|name| (0..bits).map(move |bit| (name, bit)))
+                           .map(|(name, bit)| format!("{}{:02}: {}", name, bit, rng.random_range(0..2)))
+                           .collect::<Vec<_>>();
+    let gates = swapped.into_iter().map(|(a, operator, b, output)| if rng.random_bool(0.5) {
+        format!("{} {} {} -> {}", a, operator, b, output)
+    } else {
+        format!("{} {} {} -> {}", b, operator, a, output)
+    });
+    lines(inputs) + "\n" + &lines(gates)
+}
+
+fn is_acyclic(gates: &[(String, &str, String, String)]) -> bool {
+    // Kahn's algorithm: keep evaluating gates whose inputs are known, a cycle leaves some gates behind
+    let mut known: HashSet<&str> = gates.iter()
+                                        .flat_map(|(a, _, b, _)| [a.as_str(), b.as_str()])
+                                        .filter(|wire| wire.starts_with('x') || wire.starts_with('y'))
+                                        .collect();
+    let mut pending: Vec<&(String, &str, String, String)> = gates.iter().collect();
+    loop {
+        let before = pending.len();
+        pending.retain(|(a, _, b, output)| {
+            let ready = known.contains(a.as_str()) && known.contains(b.as_str());
+            if ready {
+                known.insert(output.as_str());
+            }
+            !ready
+        });
+        if pending.is_empty() || pending.len() == before {
+            return pending.is_empty();
+        }
+    }
+}
+
+fn day_25(rng: &mut StdRng, options: &Options) -> String {
+    let schematics = (0..options.size).map(|_| {
+        let lock = rng.random_bool(0.5);
+        let heights: Vec<usize> = (0..5).map(|_| rng.random_range(0..=5)).collect();
+        let rows: Vec<String> = (0..7).map(|row| (0..5).map(|column| {
+            let filled = if lock { row <= heights[column] } else { 6 - row <= heights[column] };
+            if filled { '#' } else { '.' }
+        }).collect()).collect();
+        lines(rows)
+    });
+    schematics.collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use common::Params;
+    use crate::registry;
+
+    #[test]
+    fn generated_inputs_are_accepted_by_every_solution() {
+        let options = Options { size: 6, swaps: 2 };
+        let directory = std::env::temp_dir().join(format!("aoc-generate-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        for solution in registry::solutions() {
+            let generator = find(solution.day()).unwrap();
+            // Day 18 always solves a memory space of 70 by 70
+            let options = if solution.day() == 18 { Options { size: 70, ..options } } else { options };
+            let input = generator.generate(42, &options);
+            assert_eq!(input, generator.generate(42, &options), "{} is not reproducible", solution.name());
+
+            let filename = directory.join(format!("{}.dat", solution.name()));
+            fs::write(&filename, &input).unwrap();
+            // Only the first part, as some second parts take minutes or never finish
+            let params = Params::new(solution.params());
+            if let Err(error) = solution.run(&filename.to_string_lossy(), &solution.parts()[..1], &params) {
+                panic!("{} rejects its generated input: {}", solution.name(), error);
+            }
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
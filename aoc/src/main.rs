@@ -0,0 +1,507 @@
+mod bench;
+mod generate;
+mod registry;
+mod verify;
+
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use recording::Recording;
+use common::{describe, expected_filename, read_expected, Answer, Params, Runner};
+use log::LevelFilter;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2024 solutions")]
+struct Cli {
+    /// Logs more diagnostics, repeat for even more (`-v` info, `-vv` debug,
+    /// `-vvv` trace). Only warnings are logged by default
+    #[arg(long, short = 'v', global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Writes the log to this file instead of stderr
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A header per solution followed by a `Part N: answer` line per part
+    Text,
+    /// One `{"day", "part", "answer", "elapsed_ms"}` object per line
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves a single day, or every day when no day is given
+    Run {
+        /// Day number (`17`) or crate name (`09-part2`)
+        day: Option<String>,
+
+        /// Only solve this part of the puzzle
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Puzzle input, `-` for stdin. Defaults to the day's `input/input.dat`
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+
+        /// How to print the answers
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Reads parameters of the day from this TOML file, on top of the
+        /// `.toml` file next to the input
+        #[arg(long, requires = "day")]
+        params: Option<PathBuf>,
+
+        /// Sets a parameter of the day, e.g. `--set size=6`. See `aoc list`
+        /// for the parameters of every day
+        #[arg(long = "set", value_name = "NAME=VALUE", requires = "day")]
+        assignments: Vec<String>,
+    },
+    /// Checks every solution against the recorded answers of all its inputs
+    Verify {
+        /// Day number (`17`) or crate name (`09-part2`), defaults to every day
+        day: Option<String>,
+    },
+    /// Times parsing and solving each part of the days on their puzzle input
+    Bench {
+        /// Day number (`17`) or crate name (`09-part2`), defaults to every day
+        day: Option<String>,
+
+        /// Only time this part. Without it, only the parts with a recorded
+        /// answer for the input are timed, as the others may never finish
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// How many times to solve every part
+        #[arg(long, short = 'n', default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// Writes the timings to this JSON file, for later comparisons
+        #[arg(long)]
+        save: Option<PathBuf>,
+
+        /// Compares the timings with a file written by `--save`
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Percentage a median may be slower than the baseline before it is
+        /// flagged as a regression
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Writes a random puzzle input for a day, in the format of its input files
+    Gen {
+        /// Day number (`17`) or crate name (`09-part2`)
+        day: String,
+
+        /// How big the input gets, what it counts depends on the day. Defaults
+        /// to about the size of the real puzzle input
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// Seed for the random numbers, the same seed and options always give
+        /// the same input. Defaults to a random seed, which is logged
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// How many pairs of gate outputs to swap in the day 24 circuit
+        #[arg(long, default_value_t = 4)]
+        swaps: usize,
+
+        /// Writes the input to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+    /// Plays the states a part of a day goes through in the terminal, where
+    /// it can be paused, stepped through and sought. Prints every frame
+    /// instead when the output is not a terminal
+    Play {
+        #[command(flatten)]
+        recording: RecordingArgs,
+
+        /// Frames shown per second, `+` and `-` change it while playing
+        #[arg(long, default_value_t = 20.0)]
+        fps: f64,
+
+        /// The frame to start at, counting from 1
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        start: u64,
+
+        /// Starts out paused
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Renders the states a part of a day goes through to an image: a single
+    /// frame to a PNG file, or a sequence of them to an animated GIF
+    Export {
+        #[command(flatten)]
+        recording: RecordingArgs,
+
+        /// The file to write, a `.png` or a `.gif`
+        #[arg(long, short = 'o')]
+        output: PathBuf,
+
+        /// Width and height of a tile in pixels
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=64))]
+        scale: u32,
+
+        /// The frame to write to a PNG, counting from 1. Defaults to the last
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        frame: Option<u64>,
+
+        /// The first frame of a GIF, counting from 1
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        from: u64,
+
+        /// The last frame of a GIF. Defaults to the last frame recorded
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        to: Option<u64>,
+
+        /// Only puts every so many frames in a GIF
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        every: u64,
+
+        /// Frames shown per second in a GIF
+        #[arg(long, default_value_t = 20.0)]
+        fps: f64,
+    },
+    /// Lists the registered solutions and the parts they solve
+    List,
+}
+
+/// What `aoc play` and `aoc export` record.
+#[derive(Args)]
+struct RecordingArgs {
+    /// Day number (`6`) or crate name (`15-part2`)
+    day: String,
+
+    /// The part to record
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Puzzle input, `-` for stdin. Defaults to the day's `input/input.dat`
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Reads parameters of the day from this TOML file, on top of the
+    /// `.toml` file next to the input
+    #[arg(long)]
+    params: Option<PathBuf>,
+
+    /// Sets a parameter of the day, e.g. `--set seconds=10`
+    #[arg(long = "set", value_name = "NAME=VALUE")]
+    assignments: Vec<String>,
+}
+
+/// A line of `aoc run --format json`.
+#[derive(Serialize)]
+struct Record {
+    day: u8,
+    part: u8,
+    /// A number or a string, `null` when the part has no answer.
+    answer: Value,
+    elapsed_ms: f64,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Err(error) = init_logging(cli.verbose, cli.log_file.as_deref()) {
+        eprintln!("error: {}", error);
+        return ExitCode::FAILURE;
+    }
+
+    let result = match cli.command {
+        Command::Run { day, part, input, format, params, assignments } =>
+            run(day, part, input, format, params, &assignments),
+        Command::Verify { day } => verify(day),
+        Command::Bench { day, part, iterations, save, baseline, threshold } =>
+            bench(day, part, iterations as usize, save, baseline, threshold),
+        Command::Gen { day, size, seed, swaps, output } => generate(day, size, seed, swaps, output),
+        Command::Play { recording, fps, start, paused } => {
+            let options = animation::PlayOptions { frames_per_second: fps, start: start as usize - 1, paused };
+            play(recording, options)
+        }
+        Command::Export { recording, output, scale, frame, from, to, every, fps } => {
+            let frames = Frames { frame, from, to, every };
+            export(recording, &output, scale, frames, fps)
+        }
+        Command::List => {
+            for solution in registry::solutions() {
+                println!("{:<14} day {:>2}, parts {}", solution.name(), solution.day(), join(solution.parts()));
+                for param in solution.params() {
+                    println!("    {:<12} {:>5}  {}", param.name, param.show(param.default), param.help);
+                }
+            }
+            Ok(true)
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn init_logging(verbose: u8, log_file: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let level = match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level);
+    if let Some(log_file) = log_file {
+        let file = File::create(log_file).map_err(|error| format!("{}: {}", log_file.display(), error))?;
+        builder.target(env_logger::Target::Pipe(Box::new(file)));
+    }
+    builder.try_init()?;
+    Ok(())
+}
+
+fn run(day: Option<String>,
+       part: Option<u8>,
+       input: Option<PathBuf>,
+       format: Format,
+       params_file: Option<PathBuf>,
+       assignments: &[String]) -> Result<bool, Box<dyn Error>> {
+    let candidates = candidates(&day)?;
+
+    let mut days: Vec<u8> = candidates.iter().map(|solution| solution.day()).collect();
+    days.dedup();
+
+    for day in days {
+        let of_day: Vec<_> = candidates.iter().copied().filter(|solution| solution.day() == day).collect();
+        for (solution, parts) in registry::assign_parts(&of_day, part) {
+            let filename = input.clone().unwrap_or_else(|| default_input(solution.name()));
+            let params_file = params_file.as_deref().map(Path::to_string_lossy);
+            let params = common::resolve_params(solution, &filename.to_string_lossy(), params_file.as_deref(), assignments)?;
+            let header = format!("--- {} part {} ({}) ---", solution.name(), join(&parts), filename.display());
+            match format {
+                Format::Text => {
+                    println!("{}", header);
+                    let answers = solution.run(&filename.to_string_lossy(), &parts, &params)?;
+                    for (part, answer) in parts.iter().zip(answers) {
+                        println!("{}", describe(*part, &answer));
+                    }
+                }
+                Format::Json => {
+                    log::info!("{}", header);
+                    let run = solution.run_timed(&filename.to_string_lossy(), &parts, &params)?;
+                    for (part, answer, elapsed) in run.parts {
+                        let answer = match answer {
+                            Some(Answer::Number(number)) => json!(number),
+                            Some(Answer::Text(text)) => json!(text),
+                            None => json!(null),
+                        };
+                        let record = Record {
+                            day: solution.day(),
+                            part,
+                            answer,
+                            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+                        };
+                        println!("{}", serde_json::to_string(&record)?);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+fn verify(day: Option<String>) -> Result<bool, Box<dyn Error>> {
+    let mut checks = Vec::new();
+    for solution in candidates(&day)? {
+        checks.extend(verify::verify(solution, &input_dir(solution.name()))?);
+    }
+    verify::print_table(&checks);
+    Ok(checks.iter().all(|check| check.status != verify::Status::Fail))
+}
+
+fn bench(day: Option<String>,
+         part: Option<u8>,
+         iterations: usize,
+         save: Option<PathBuf>,
+         baseline: Option<PathBuf>,
+         threshold: f64) -> Result<bool, Box<dyn Error>> {
+    let baseline = baseline.map(|baseline| bench::read_baseline(&baseline)).transpose()?;
+
+    let mut measurements = Vec::new();
+    for solution in candidates(&day)? {
+        let filename = default_input(solution.name()).to_string_lossy().to_string();
+        let params = Params::for_input(solution.params(), &filename)?;
+        let parts: Vec<u8> = match part {
+            Some(part) => solution.parts().iter().copied().filter(|p| *p == part).collect(),
+            None => {
+                let expected = read_expected(&expected_filename(&filename)).unwrap_or_default();
+                solution.parts().iter().copied().filter(|p| expected.contains_key(p)).collect()
+            }
+        };
+        if parts.is_empty() {
+            continue;
+        }
+        log::info!("timing {} part {}", solution.name(), join(&parts));
+        measurements.extend(bench::bench(solution, &filename, &parts, &params, iterations)?);
+    }
+
+    let no_regressions = bench::print_table(&measurements, baseline.as_deref(), threshold);
+    if let Some(save) = save {
+        bench::save_baseline(&save, &measurements)?;
+    }
+    Ok(no_regressions)
+}
+
+fn generate(day: String,
+            size: Option<usize>,
+            seed: Option<u64>,
+            swaps: usize,
+            output: Option<PathBuf>) -> Result<bool, Box<dyn Error>> {
+    let solution = candidates(&Some(day))?[0];
+    let generator = generate::find(solution.day())
+        .ok_or_else(|| format!("No input generator for day {}", solution.day()))?;
+
+    let seed = seed.unwrap_or_else(rand::random);
+    let size = size.unwrap_or(generator.default_size);
+    log::info!("generating day {} with seed {} and {} {}", generator.day, seed, size, generator.size);
+    let options = generate::Options { size, swaps };
+    let input = generator.generate(seed, &options);
+    let params = generator.params(&options);
+
+    match output {
+        Some(output) => {
+            fs::write(&output, input).map_err(|error| format!("{}: {}", output.display(), error))?;
+            // The day reads the parameters the input needs from the file next to it
+            if !params.is_empty() {
+                let filename = common::params_filename(&output.to_string_lossy());
+                let toml: String = params.iter().map(|(name, value)| format!("{} = {}\n", name, value)).collect();
+                log::info!("writing the parameters of the input to {}", filename);
+                fs::write(&filename, toml).map_err(|error| format!("{}: {}", filename, error))?;
+            }
+        }
+        None => {
+            io::stdout().write_all(input.as_bytes())?;
+            if !params.is_empty() {
+                let flags: Vec<String> = params.iter().map(|(name, value)| format!("--set {}={}", name, value)).collect();
+                log::warn!("run day {} on this input with {}", generator.day, flags.join(" "));
+            }
+        }
+    }
+    Ok(true)
+}
+
+fn record(args: RecordingArgs) -> Result<Recording, Box<dyn Error>> {
+    let RecordingArgs { day, part, input, params: params_file, assignments } = args;
+    let candidates = candidates(&Some(day))?;
+    let Some((solution, _)) = registry::assign_parts(&candidates, Some(part)).into_iter().next() else {
+        return Err(format!("{} does not solve part {}", candidates[0].name(), part).into());
+    };
+
+    let filename = input.unwrap_or_else(|| default_input(solution.name()));
+    let params_file = params_file.as_deref().map(Path::to_string_lossy);
+    let params = common::resolve_params(solution, &filename.to_string_lossy(), params_file.as_deref(), &assignments)?;
+    log::info!("recording {} part {} ({})", solution.name(), part, filename.display());
+    let recording = solution.record(&filename.to_string_lossy(), part, &params)?
+        .ok_or_else(|| format!("{} has nothing to record for part {}", solution.name(), part))?;
+    log::info!("recorded {} frames", recording.len());
+    Ok(recording)
+}
+
+fn play(args: RecordingArgs, options: animation::PlayOptions) -> Result<bool, Box<dyn Error>> {
+    let recording = record(args)?;
+    if io::stdout().is_terminal() {
+        animation::play(&recording, options)?;
+    } else {
+        animation::print(&recording, &mut io::stdout().lock())?;
+    }
+    Ok(true)
+}
+
+/// The frames `aoc export` writes, counting from 1.
+struct Frames {
+    /// The single frame of a PNG.
+    frame: Option<u64>,
+    from: u64,
+    to: Option<u64>,
+    every: u64,
+}
+
+fn export(args: RecordingArgs,
+          output: &Path,
+          scale: u32,
+          frames: Frames,
+          frames_per_second: f64) -> Result<bool, Box<dyn Error>> {
+    let is_gif = match output.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("gif") => true,
+        Some(extension) if extension.eq_ignore_ascii_case("png") => false,
+        _ => return Err(format!("{}: expected a `.png` or `.gif` file", output.display()).into()),
+    };
+
+    let recording = record(args)?;
+    let last = recording.len() as u64;
+    let check = |frame: u64| if frame <= last {
+        Ok(frame as usize - 1)
+    } else {
+        Err(format!("There are only {} frames", last))
+    };
+    let file = File::create(output).map_err(|error| format!("{}: {}", output.display(), error))?;
+    let mut out = io::BufWriter::new(file);
+
+    if is_gif {
+        let from = check(frames.from)?;
+        let to = check(frames.to.unwrap_or(last))?;
+        let indices: Vec<usize> = (from..=to).step_by(frames.every as usize).collect();
+        log::info!("writing {} frames to {}", indices.len(), output.display());
+        animation::write_gif(&recording, &indices, scale, frames_per_second, &mut out)?;
+    } else {
+        let frame = check(frames.frame.unwrap_or(last))?;
+        log::info!("writing frame {} ({}) to {}", frame + 1, recording.caption(frame), output.display());
+        animation::write_png(&recording.frame(frame), scale, &mut out)?;
+    }
+    out.flush()?;
+    Ok(true)
+}
+
+fn candidates(day: &Option<String>) -> Result<Vec<&'static dyn Runner>, Box<dyn Error>> {
+    let candidates = match day {
+        Some(day) => registry::find(day),
+        None => registry::solutions(),
+    };
+    if candidates.is_empty() {
+        return Err(format!("No solution found for day {}", day.clone().unwrap_or_default()).into());
+    }
+    Ok(candidates)
+}
+
+/// `<workspace>/<crate>/input`, independent of the working directory.
+fn input_dir(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent()
+                                         .unwrap()
+                                         .join(name)
+                                         .join("input")
+}
+
+fn default_input(name: &str) -> PathBuf {
+    input_dir(name).join("input.dat")
+}
+
+fn join(parts: &[u8]) -> String {
+    parts.iter().map(|part| part.to_string()).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,72 @@
+use common::Runner;
+
+/// Every solution in the workspace, in order of the day they solve.
+pub fn solutions() -> Vec<&'static dyn Runner> {
+    vec![
+        &day_01::Day01,
+        &day_02::Day02,
+        &day_03::Day03,
+        &day_04::Day04,
+        &day_05::Day05,
+        &day_06::Day06,
+        &day_07::Day07,
+        &day_08::Day08,
+        &day_09::Day09,
+        &day_09_part2::Day09Part2,
+        &day_10::Day10,
+        &day_11::Day11,
+        &day_12::Day12,
+        &day_13::Day13,
+        &day_14::Day14,
+        &day_15::Day15,
+        &day_15_part2::Day15Part2,
+        &day_16::Day16,
+        &day_17::Day17,
+        &day_18::Day18,
+        &day_19::Day19,
+        &day_20::Day20,
+        &day_20_part2::Day20Part2,
+        &day_21::Day21,
+        &day_22::Day22,
+        &day_23::Day23,
+        &day_24::Day24,
+        &day_25::Day25,
+    ]
+}
+
+/// Finds the solutions a day argument refers to. Plain numbers (`9`, `09`)
+/// select every crate solving that day, crate names with or without the
+/// `day-` prefix (`09-part2`, `day-09-part2`) select a single crate.
+pub fn find(day: &str) -> Vec<&'static dyn Runner> {
+    let solutions = solutions();
+    match day.parse::<u8>() {
+        Ok(number) => solutions.into_iter()
+                               .filter(|solution| solution.day() == number)
+                               .collect(),
+        Err(_) => {
+            let name = if day.starts_with("day-") { day.to_string() } else { format!("day-{}", day) };
+            solutions.into_iter()
+                     .filter(|solution| solution.name() == name)
+                     .collect()
+        }
+    }
+}
+
+/// Assigns every part to the first of the given solutions that solves it, so
+/// days with a separate `-part2` crate run each part exactly once.
+pub fn assign_parts(candidates: &[&'static dyn Runner],
+                    part: Option<u8>) -> Vec<(&'static dyn Runner, Vec<u8>)> {
+    let wanted: Vec<u8> = part.map_or(vec![1, 2], |part| vec![part]);
+
+    let mut assignments: Vec<(&'static dyn Runner, Vec<u8>)> = Vec::new();
+    for part in wanted {
+        let Some(solution) = candidates.iter().find(|solution| solution.parts().contains(&part)) else {
+            continue;
+        };
+        match assignments.iter_mut().find(|(assigned, _)| assigned.name() == solution.name()) {
+            Some((_, parts)) => parts.push(part),
+            None => assignments.push((*solution, vec![part])),
+        }
+    }
+    assignments
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use common::{expected_filename, read_expected, Answer, Params, Runner};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There is no recorded answer, so the part was not run at all.
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// The outcome of one part of one solution on one input file.
+pub struct Check {
+    pub name: &'static str,
+    pub input: String,
+    pub part: u8,
+    pub expected: Option<Answer>,
+    pub actual: Option<String>,
+    pub status: Status,
+}
+
+/// Runs `solution` on every `*.dat` file in `input_dir`, comparing the answers
+/// with the ones recorded in the matching `.expected` files. Parts without a
+/// recorded answer are reported as missing without being run, as some of
+/// them take minutes or never finish on the example inputs.
+pub fn verify(solution: &dyn Runner, input_dir: &Path) -> io::Result<Vec<Check>> {
+    let mut checks = Vec::new();
+
+    for input in inputs(input_dir)? {
+        let filename = input.to_string_lossy().to_string();
+        let expected_file = expected_filename(&filename);
+        let expected = if Path::new(&expected_file).exists() {
+            read_expected(&expected_file)?
+        } else {
+            Default::default()
+        };
+
+        let parts: Vec<u8> = solution.parts()
+                                     .iter()
+                                     .copied()
+                                     .filter(|part| expected.contains_key(part))
+                                     .collect();
+        let answers = run_catching_panics(solution, &filename, &parts);
+
+        let input_name = input.file_name().unwrap().to_string_lossy().to_string();
+        for &part in solution.parts() {
+            let expected = expected.get(&part).cloned();
+            let (actual, status) = match (&expected, parts.iter().position(|p| *p == part)) {
+                (Some(expected), Some(index)) => match &answers {
+                    Ok(answers) => match &answers[index] {
+                        Some(answer) if answer == expected => (Some(answer.to_string()), Status::Pass),
+                        Some(answer) => (Some(answer.to_string()), Status::Fail),
+                        None => (Some("no answer".to_string()), Status::Fail),
+                    },
+                    Err(error) => (Some(error.clone()), Status::Fail),
+                },
+                _ => (None, Status::Missing),
+            };
+            checks.push(Check { name: solution.name(), input: input_name.clone(), part, expected, actual, status });
+        }
+    }
+
+    Ok(checks)
+}
+
+/// Prints the checks as a table, followed by a summary line.
+pub fn print_table(checks: &[Check]) {
+    let width = |f: &dyn Fn(&Check) -> usize, header: &str| {
+        checks.iter().map(f).max().unwrap_or(0).max(header.len())
+    };
+    let name_width = width(&|check| check.name.len(), "solution");
+    let input_width = width(&|check| check.input.len(), "input");
+    let expected_width = width(&|check| check.expected.as_ref().map_or(1, |e| e.to_string().len()), "expected");
+
+    println!("{:<name_width$}  {:<input_width$}  part  {:<expected_width$}  {:<7}  actual",
+             "solution", "input", "expected", "status");
+    for check in checks {
+        println!("{:<name_width$}  {:<input_width$}  {:>4}  {:<expected_width$}  {:<7}  {}",
+                 check.name,
+                 check.input,
+                 check.part,
+                 check.expected.as_ref().map_or("-".to_string(), |e| e.to_string()),
+                 check.status,
+                 check.actual.as_deref().unwrap_or("-"));
+    }
+
+    let count = |status: Status| checks.iter().filter(|check| check.status == status).count();
+    println!();
+    println!("{} passed, {} failed, {} missing",
+             count(Status::Pass), count(Status::Fail), count(Status::Missing));
+}
+
+fn inputs(input_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs: Vec<PathBuf> = fs::read_dir(input_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|extension| extension == "dat"))
+        .collect();
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Runs the parts, turning errors and panics into a message for the table so
+/// a single broken day does not stop the whole verification.
+fn run_catching_panics(solution: &dyn Runner,
+                       filename: &str,
+                       parts: &[u8]) -> Result<Vec<Option<Answer>>, String> {
+    if parts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let run = || Params::for_input(solution.params(), filename).and_then(|params| solution.run(filename, parts, &params));
+    match panic::catch_unwind(AssertUnwindSafe(run)) {
+        Ok(Ok(answers)) => Ok(answers),
+        // Only the first line of a parse error, the table has no room for the whole diagnostic
+        Ok(Err(error)) => Err(format!("error: {}", error.to_string().lines().next().unwrap_or_default())),
+        Err(_) => Err("panicked".to_string()),
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+/// The answer to one part of a puzzle. Most answers are numbers, a few days
+/// ask for a string such as day 17's program output or day 23's password.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Anything that reads as a number is a number, everything else is text.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(s.parse::<i64>().map_or_else(|_| Answer::Text(s.to_string()), Answer::Number))
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($number:ty),*) => {
+        $(impl From<$number> for Answer {
+            fn from(number: $number) -> Self {
+                Answer::Number(number as i64)
+            }
+        })*
+    };
+}
+
+answer_from_number!(usize, isize, u64, i64, u32, i32);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// The path of the file holding the known answers for `input`, which is the
+/// input's own path with `.expected` appended (`test.dat.expected`).
+pub fn expected_filename(input: &str) -> String {
+    format!("{}.expected", input)
+}
+
+/// Reads the known answers recorded for an input, one `partN: answer` line per
+/// part. Parts without a line have no known answer. Blank lines and lines
+/// starting with `#` are ignored.
+pub fn read_expected(filename: &str) -> io::Result<BTreeMap<u8, Answer>> {
+    let content = fs::read_to_string(filename)?;
+    let mut expected = BTreeMap::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let part = line.split_once(':')
+                       .and_then(|(key, answer)| {
+                           key.trim().strip_prefix("part")?.parse::<u8>().ok().map(|part| (part, answer))
+                       });
+
+        match part {
+            Some((part, answer)) => { expected.insert(part, answer.parse().unwrap()); }
+            None => return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                              format!("{}:{}: expected `partN: answer`, found `{}`",
+                                                      filename, index + 1, line))),
+        }
+    }
+
+    Ok(expected)
+}
@@ -0,0 +1,318 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// A puzzle input that does not look the way the reader expected, pinned
+/// down to the line and column where things went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// What the reader was looking for, e.g. `a number`.
+    pub expected: String,
+    /// The offending line, empty when the input ended too early.
+    pub text: String,
+}
+
+impl fmt::Display for ParseError {
+    /// Renders the error the way compilers do, quoting the line and pointing
+    /// at the column:
+    ///
+    /// ```text
+    /// input/test.dat:3:13: expected a number
+    ///   |
+    /// 3 | Button A: X+a4, Y+34
+    ///   |             ^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:{}:{}: expected {}", self.file, self.line, self.column, self.expected)?;
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl Error for ParseError {}
+
+/// Everything that can go wrong while reading a puzzle input.
+#[derive(Debug)]
+pub enum InputError {
+    /// The file could not be read at all.
+    Io { file: String, error: io::Error },
+    /// The file was read, but its content is not a valid input.
+    Parse(ParseError),
+    /// The parameters given for the input are not the ones the day has.
+    Params(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io { file, error } => write!(f, "{}: {}", file, error),
+            InputError::Parse(error) => write!(f, "{}", error),
+            InputError::Params(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { error, .. } => Some(error),
+            InputError::Parse(error) => Some(error),
+            InputError::Params(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for InputError {
+    fn from(error: ParseError) -> Self {
+        InputError::Parse(error)
+    }
+}
+
+/// One line of an input, remembering where it came from so errors can point
+/// back at it.
+#[derive(Debug, Clone)]
+pub struct Line {
+    file: Rc<str>,
+    pub number: usize,
+    pub text: String,
+}
+
+impl Line {
+    /// An error at the given 1-based `column` of this line.
+    pub fn error(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            file: self.file.to_string(),
+            line: self.number,
+            column,
+            expected: expected.into(),
+            text: self.text.clone(),
+        }
+    }
+
+    /// An error at the start of `part`, which must be a slice of this line's
+    /// text, as handed out by `split`, `split_whitespace` or regex captures.
+    /// Anything else is reported just past the end of the line.
+    pub fn error_at(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        self.error(self.column_of(part), expected)
+    }
+
+    /// Parses `part` of this line, reporting `expected` when that fails.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.trim().parse::<T>().map_err(|_| self.error_at(part, expected))
+    }
+
+    /// Splits the line around the first `separator`.
+    pub fn split_once<'a>(&'a self, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text.split_once(separator)
+                 .ok_or_else(|| self.error(self.text.chars().count() + 1, format!("`{}`", separator)))
+    }
+
+    /// Converts every character of the line with `f`, reporting the first
+    /// character it rejects.
+    pub fn parse_chars<T>(&self, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<Vec<T>, ParseError> {
+        self.text.chars()
+                 .enumerate()
+                 .map(|(index, c)| f(c).ok_or_else(|| self.error(index + 1, expected)))
+                 .collect()
+    }
+
+    fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            self.text.chars().count() + 1
+        }
+    }
+}
+
+/// The lines of an input, in order.
+pub struct Lines {
+    file: Rc<str>,
+    lines: std::vec::IntoIter<Line>,
+    count: usize,
+}
+
+impl Iterator for Lines {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Line> {
+        self.lines.next()
+    }
+}
+
+impl Lines {
+    /// The next line, or an error at the end of the input when there is none.
+    pub fn expect(&mut self, expected: &str) -> Result<Line, ParseError> {
+        self.lines.next().ok_or_else(|| self.end_of_input(expected))
+    }
+
+    /// An error for an input that ends while `expected` is still missing.
+    pub fn end_of_input(&self, expected: &str) -> ParseError {
+        ParseError {
+            file: self.file.to_string(),
+            line: self.count + 1,
+            column: 1,
+            expected: format!("{}, found the end of the input", expected),
+            text: String::new(),
+        }
+    }
+
+    /// Reads the remaining lines as the rows of a rectangular map, converting
+    /// every character with `f`.
+    pub fn grid_rows<T>(self, expected: &str, f: impl Fn(char) -> Option<T>) -> Result<Vec<Vec<T>>, ParseError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in self {
+            let row = line.parse_chars(expected, &f)?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(line.error(row.len().min(first.len()) + 1,
+                                          format!("a row of {} cells", first.len())));
+                }
+            }
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    /// The lines of an input held in memory, such as an example embedded in a
+    /// test. `name` stands in for the file name in errors.
+    pub fn from_text(name: &str, text: &str) -> Self {
+        Lines::from_vec(name, text.lines().map(str::to_string).collect())
+    }
+
+    /// Reads all lines from `reader`, which errors then name `name`.
+    pub fn from_reader(name: &str, reader: impl BufRead) -> Result<Self, InputError> {
+        let texts = reader.lines()
+                          .collect::<io::Result<Vec<String>>>()
+                          .map_err(|error| InputError::Io { file: name.to_string(), error })?;
+        Ok(Lines::from_vec(name, texts))
+    }
+
+    fn from_vec(name: &str, texts: Vec<String>) -> Self {
+        let file: Rc<str> = Rc::from(name);
+        let lines: Vec<Line> = texts.into_iter()
+                                    .enumerate()
+                                    .map(|(index, text)| Line { file: file.clone(), number: index + 1, text })
+                                    .collect();
+        let count = lines.len();
+        Lines { file, lines: lines.into_iter(), count }
+    }
+}
+
+/// The lines of an input read one at a time as they are needed, for inputs too
+/// large to hold in memory. [`Lines`] reads everything up front instead.
+pub struct LineStream<R> {
+    file: Rc<str>,
+    reader: R,
+    number: usize,
+}
+
+impl<R: BufRead> LineStream<R> {
+    /// Reads lines from `reader`, which errors then name `name`.
+    pub fn new(name: &str, reader: R) -> Self {
+        LineStream { file: Rc::from(name), reader, number: 0 }
+    }
+}
+
+impl<R: BufRead> Iterator for LineStream<R> {
+    type Item = Result<Line, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = String::new();
+        match self.reader.read_line(&mut text) {
+            Ok(0) => None,
+            Ok(_) => {
+                // Strips the line ending the way `BufRead::lines` does
+                if text.ends_with('\n') {
+                    text.pop();
+                    if text.ends_with('\r') {
+                        text.pop();
+                    }
+                }
+                self.number += 1;
+                Some(Ok(Line { file: self.file.clone(), number: self.number, text }))
+            }
+            Err(error) => Some(Err(InputError::Io { file: self.file.to_string(), error })),
+        }
+    }
+}
+
+/// Streams the lines of `filename`, or of standard input when it is
+/// [`STDIN`].
+pub fn stream_lines(filename: &str) -> Result<LineStream<Box<dyn BufRead>>, InputError> {
+    if filename == STDIN {
+        return Ok(LineStream::new("<stdin>", Box::new(io::stdin().lock())));
+    }
+    let file = File::open(filename)
+        .map_err(|error| InputError::Io { file: filename.to_string(), error })?;
+    Ok(LineStream::new(filename, Box::new(BufReader::new(file))))
+}
+
+/// The file name that makes [`read_lines`] read standard input instead.
+pub const STDIN: &str = "-";
+
+/// Reads `filename` as lines, or standard input when it is [`STDIN`].
+pub fn read_lines(filename: &str) -> Result<Lines, InputError> {
+    if filename == STDIN {
+        return Lines::from_reader("<stdin>", io::stdin().lock());
+    }
+    let file = File::open(filename)
+        .map_err(|error| InputError::Io { file: filename.to_string(), error })?;
+    Lines::from_reader(filename, BufReader::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line {
+        Line { file: Rc::from("test.dat"), number: 3, text: text.to_string() }
+    }
+
+    #[test]
+    fn errors_point_at_the_part_that_failed_to_parse() {
+        let line = line("Button A: X+9a, Y+34");
+        let x = line.text.split(", ").next().unwrap().split('+').nth(1).unwrap();
+        let error = line.parse::<usize>(x, "a number").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 13));
+        assert_eq!(error.to_string(),
+                   "test.dat:3:13: expected a number\n  |\n3 | Button A: X+9a, Y+34\n  |             ^");
+    }
+
+    #[test]
+    fn grid_rows_must_all_have_the_same_length() {
+        let lines = Lines::from_text("test.dat", "#.#\n#.");
+        let error = lines.grid_rows("a cell", Some).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a row of 3 cells");
+    }
+
+    #[test]
+    fn readers_and_strings_give_the_same_lines() {
+        let text = "3   4\r\n4   3\r\n";
+        let from_reader: Vec<String> = Lines::from_reader("<stdin>", text.as_bytes()).unwrap().map(|line| line.text).collect();
+        let from_text: Vec<String> = Lines::from_text("<string>", text).map(|line| line.text).collect();
+        assert_eq!(from_reader, vec!["3   4", "4   3"]);
+        assert_eq!(from_reader, from_text);
+
+        let streamed: Vec<String> = LineStream::new("<stdin>", text.as_bytes()).map(|line| line.unwrap().text).collect();
+        assert_eq!(streamed, from_text);
+
+        let mut lines = Lines::from_text("<string>", text);
+        lines.nth(1);
+        assert_eq!(lines.end_of_input("a pair").to_string().lines().next(),
+                   Some("<string>:3:1: expected a pair, found the end of the input"));
+    }
+}
@@ -0,0 +1,348 @@
+mod answer;
+pub mod input;
+mod params;
+
+use std::io::BufRead;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use clap::{Args, Parser};
+use recording::Recording;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+pub use answer::{expected_filename, read_expected, Answer};
+pub use input::{stream_lines, InputError, Line, LineStream, Lines, ParseError};
+pub use params::{params_filename, Param, Params};
+use input::{read_lines, STDIN};
+
+/// A single day of the puzzle, as implemented by one of the `day-*` crates.
+///
+/// Every day crate exposes a unit struct implementing this trait, which is
+/// what the `aoc` runner uses to find and run it.
+pub trait Solution {
+    type Problem;
+
+    /// Name of the crate implementing the solution, e.g. `day-09-part2`.
+    fn name(&self) -> &'static str;
+
+    fn day(&self) -> u8;
+
+    /// The parts of the puzzle this crate solves. Days that split their second
+    /// part into a separate crate only claim one of them.
+    fn parts(&self) -> &'static [u8] {
+        &[1, 2]
+    }
+
+    /// The constants of the puzzle that the examples change, such as the size
+    /// of a map.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Parses the lines of an input into the problem the parts solve.
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError>;
+
+    /// Hands the values of the [`Solution::params`] to a parsed problem,
+    /// failing for values the problem cannot hold.
+    fn configure(&self, _problem: &mut Self::Problem, _params: &Params) -> Result<(), InputError> {
+        Ok(())
+    }
+
+    /// Reads and parses `filename`, or standard input when it is `-`.
+    fn read_input(&self, filename: &str, params: &Params) -> Result<Self::Problem, InputError> {
+        let mut problem = self.parse(read_lines(filename)?)?;
+        self.configure(&mut problem, params)?;
+        Ok(problem)
+    }
+
+    /// Reads and parses everything `reader` provides, naming it `name` in
+    /// errors.
+    fn read_from(&self, name: &str, reader: impl BufRead, params: &Params) -> Result<Self::Problem, InputError> {
+        let mut problem = self.parse(Lines::from_reader(name, reader)?)?;
+        self.configure(&mut problem, params)?;
+        Ok(problem)
+    }
+
+    /// Parses an input held in a string, such as an example embedded in a test.
+    fn read_str(&self, input: &str, params: &Params) -> Result<Self::Problem, InputError> {
+        let mut problem = self.parse(Lines::from_text("<string>", input))?;
+        self.configure(&mut problem, params)?;
+        Ok(problem)
+    }
+
+    /// The parameters at their defaults, as the real puzzle input uses them.
+    fn default_params(&self) -> Params {
+        Params::new(self.params())
+    }
+
+    /// The answer to the first part, `None` when there is none to give.
+    fn solve1(&self, _problem: &Self::Problem) -> Option<Answer> {
+        None
+    }
+
+    /// The answer to the second part, `None` when there is none to give.
+    fn solve2(&self, _problem: &Self::Problem) -> Option<Answer> {
+        None
+    }
+
+    /// The states a part goes through, for `aoc play`. `None` for the parts
+    /// that have nothing to show.
+    fn record(&self, _problem: &Self::Problem, _part: u8) -> Option<Recording> {
+        None
+    }
+}
+
+/// Object safe view on a [`Solution`], so solutions with different problem
+/// types can live in the same registry.
+pub trait Runner {
+    fn name(&self) -> &'static str;
+
+    fn day(&self) -> u8;
+
+    fn parts(&self) -> &'static [u8];
+
+    fn params(&self) -> &'static [Param];
+
+    /// Reads `filename` once and solves the requested `parts` on it, returning
+    /// the answers in the same order.
+    fn run(&self, filename: &str, parts: &[u8], params: &Params) -> Result<Vec<Option<Answer>>, InputError> {
+        Ok(self.run_timed(filename, parts, params)?.answers())
+    }
+
+    /// Like [`Runner::run`], but also measures how long reading the input and
+    /// solving every part took.
+    fn run_timed(&self, filename: &str, parts: &[u8], params: &Params) -> Result<TimedRun, InputError>;
+
+    /// Reads `filename` and records the states `part` goes through, `None`
+    /// when the day has no recording for it.
+    fn record(&self, filename: &str, part: u8, params: &Params) -> Result<Option<Recording>, InputError>;
+}
+
+/// The answers of a [`Runner::run_timed`] call together with their timings.
+#[derive(Debug, Clone)]
+pub struct TimedRun {
+    pub parse: Duration,
+    /// The part, its answer and the time it took, in the order requested.
+    pub parts: Vec<(u8, Option<Answer>, Duration)>,
+}
+
+impl TimedRun {
+    pub fn answers(self) -> Vec<Option<Answer>> {
+        self.parts.into_iter().map(|(_, answer, _)| answer).collect()
+    }
+}
+
+impl<S: Solution> Runner for S {
+    fn name(&self) -> &'static str {
+        Solution::name(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        Solution::parts(self)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Solution::params(self)
+    }
+
+    fn run_timed(&self, filename: &str, parts: &[u8], params: &Params) -> Result<TimedRun, InputError> {
+        let start = Instant::now();
+        let problem = self.read_input(filename, params)?;
+        let parse = start.elapsed();
+
+        let parts = parts.iter()
+                         .map(|&part| {
+                             let start = Instant::now();
+                             let answer = match part {
+                                 1 => self.solve1(&problem),
+                                 2 => self.solve2(&problem),
+                                 _ => panic!("There is no part {}", part),
+                             };
+                             (part, answer, start.elapsed())
+                         })
+                         .collect();
+        Ok(TimedRun { parse, parts })
+    }
+
+    fn record(&self, filename: &str, part: u8, params: &Params) -> Result<Option<Recording>, InputError> {
+        let problem = self.read_input(filename, params)?;
+        Ok(Solution::record(self, &problem, part))
+    }
+}
+
+/// What a day binary takes to solve a puzzle input.
+#[derive(Debug, Clone, Args)]
+pub struct SolveArgs {
+    /// Reads the parameters of the day from this TOML file, on top of the
+    /// `.toml` file next to the input
+    #[arg(long)]
+    pub params: Option<String>,
+
+    /// Sets a parameter of the day, e.g. `--set size=6`
+    #[arg(long = "set", value_name = "NAME=VALUE")]
+    pub assignments: Vec<String>,
+
+    /// Puzzle input, `-` for stdin
+    pub input: String,
+}
+
+/// The arguments of a day binary that only solves its puzzle input.
+#[derive(Parser)]
+#[command(about = None)]
+struct SolveCommand {
+    #[command(flatten)]
+    solve: SolveArgs,
+}
+
+/// Parses `args` into the command line `C` of the binary of `solution`,
+/// naming it after the day and listing the parameters of the day in the
+/// help. Exits with the usage on errors, or the help when asked for it.
+pub fn parse_args<C: Parser>(solution: &dyn Runner, args: &[String]) -> C {
+    let mut command = C::command().name(solution.name());
+    if command.get_about().is_none() {
+        command = command.about(format!("Solves day {} for a puzzle input", solution.day()));
+    }
+    let params = solution.params();
+    if !params.is_empty() {
+        let width = params.iter().map(|param| param.name.len()).max().unwrap_or(0);
+        let lines: Vec<String> = params.iter()
+                                       .map(|param| format!("  {:<width$}  {} (default {})", param.name, param.help, param.show(param.default)))
+                                       .collect();
+        let mut help = format!("Parameters:\n{}", lines.join("\n"));
+        if let Some(after) = command.get_after_help() {
+            help = format!("{}\n\n{}", help, after);
+        }
+        command = command.after_help(help);
+    }
+    let matches = command.get_matches_from(args);
+    C::from_arg_matches(&matches).unwrap_or_else(|error| error.exit())
+}
+
+/// Solves every part of the day for the input of `args` and prints the
+/// answers.
+pub fn solve<S: Solution>(solution: &S, args: &SolveArgs) -> ExitCode {
+    // Another logger may be set up already, by a binary that wants more
+    if log::set_logger(&WARNINGS).is_ok() {
+        log::set_max_level(LevelFilter::Warn);
+    }
+
+    let parts = Solution::parts(solution);
+    let answers = resolve_params(solution, &args.input, args.params.as_deref(), &args.assignments)
+        .and_then(|params| Runner::run(solution, &args.input, parts, &params));
+    match answers {
+        Ok(answers) => {
+            for (part, answer) in parts.iter().zip(answers) {
+                println!("{}", describe(*part, &answer));
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Entry point shared by the per day binaries that only solve their puzzle
+/// input: solves every part of the day for the file given as the argument,
+/// or standard input for `-`, and prints the answers. `--params <file>` and
+/// `--set <name>=<value>` override the parameters of the day, after those in
+/// the `.toml` file next to the input.
+pub fn run_main<S: Solution>(solution: &S, args: &[String]) -> ExitCode {
+    let command: SolveCommand = parse_args(solution, args);
+    solve(solution, &command.solve)
+}
+
+/// Shows the warnings and errors the solvers log on standard error, such as
+/// why a part has no answer.
+struct Warnings;
+
+static WARNINGS: Warnings = Warnings;
+
+impl Log for Warnings {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let level = if record.level() == Level::Error { "error" } else { "warning" };
+            eprintln!("{}: {}", level, record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// The parameters for running `solution` on `input`: the defaults, then the
+/// `.toml` file next to the input, then `params_file`, then the `name=value`
+/// assignments. Standard input has no `.toml` file.
+pub fn resolve_params(solution: &dyn Runner,
+                      input: &str,
+                      params_file: Option<&str>,
+                      assignments: &[impl AsRef<str>]) -> Result<Params, InputError> {
+    let mut params = if input == STDIN {
+        Params::new(solution.params())
+    } else {
+        Params::for_input(solution.params(), input)?
+    };
+    if let Some(params_file) = params_file {
+        params.read_toml(params_file)?;
+    }
+    for assignment in assignments {
+        params.assign(assignment.as_ref())?;
+    }
+    Ok(params)
+}
+
+/// One line describing the answer to a part, as printed by the runners.
+pub fn describe(part: u8, answer: &Option<Answer>) -> String {
+    match answer {
+        Some(answer) => format!("Part {}: {}", part, answer),
+        None => format!("Part {}: no answer", part),
+    }
+}
+
+/// Solves `filename` and asserts that every part with an answer recorded in
+/// its `.expected` file gets that answer, using the parameters in its `.toml`
+/// file when there is one. Backs the tests generated by
+/// [`example_tests!`].
+pub fn assert_expected<S: Solution>(solution: &S, filename: &str) {
+    let expected = read_expected(&expected_filename(filename))
+        .unwrap_or_else(|error| panic!("Cannot read the answers for {}: {}", filename, error));
+    let parts: Vec<u8> = Solution::parts(solution).iter()
+                                                  .copied()
+                                                  .filter(|part| expected.contains_key(part))
+                                                  .collect();
+    assert!(!parts.is_empty(), "{} has no answers recorded for {}", filename, Solution::name(solution));
+
+    let answers = Params::for_input(Solution::params(solution), filename)
+        .and_then(|params| Runner::run(solution, filename, &parts, &params))
+        .unwrap_or_else(|error| panic!("Cannot read {}: {}", filename, error));
+    for (part, answer) in parts.iter().zip(answers) {
+        assert_eq!(answer.as_ref(), expected.get(part), "part {} of {}", part, filename);
+    }
+}
+
+/// Generates a test per example input of a day, checking it against the
+/// recorded answers. The names are the input files in the crate's `input`
+/// directory, without their `.dat` extension.
+///
+/// ```ignore
+/// common::example_tests!(day_12::Day12; test, test2, test3);
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:expr; $($input:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $input() {
+                $crate::assert_expected(&$solution,
+                                        concat!(env!("CARGO_MANIFEST_DIR"), "/input/", stringify!($input), ".dat"));
+            }
+        )+
+    };
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::InputError;
+
+/// A constant of a puzzle that the examples change, such as the size of a
+/// map or the number of rounds to simulate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value the real puzzle input uses.
+    pub default: usize,
+    pub help: &'static str,
+    /// The smallest value the day can work with.
+    pub min: usize,
+    /// The largest value the day can work with.
+    pub max: usize,
+    /// Names for the values, which the value is the index of, for a parameter
+    /// that picks one of a few choices.
+    pub names: &'static [&'static str],
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: usize, help: &'static str) -> Self {
+        Param { name, default, help, min: 0, max: usize::MAX, names: &[] }
+    }
+
+    /// Rejects values below `min`.
+    pub const fn at_least(self, min: usize) -> Self {
+        Param { min, ..self }
+    }
+
+    /// Rejects values above `max`.
+    pub const fn at_most(self, max: usize) -> Self {
+        Param { max, ..self }
+    }
+
+    /// Makes the parameter one of `names`, given either by name or by its
+    /// index.
+    pub const fn named(self, names: &'static [&'static str]) -> Self {
+        Param { min: 0, max: names.len() - 1, names, ..self }
+    }
+
+    /// `value` as it is written, by its name if it has one.
+    pub fn show(&self, value: usize) -> String {
+        self.names.get(value).map_or_else(|| value.to_string(), |name| name.to_string())
+    }
+
+    /// The value `text` names, either a number or one of the `names`.
+    fn parse(&self, text: &str) -> Result<usize, String> {
+        if let Some(index) = self.names.iter().position(|name| *name == text) {
+            return Ok(index);
+        }
+        text.parse().map_err(|_| format!("`{}` must be {}, found `{}`", self.name, self.expected(), text))
+    }
+
+    fn expected(&self) -> String {
+        if self.names.is_empty() {
+            "a non-negative number".to_string()
+        } else {
+            format!("one of {}", self.names.join(", "))
+        }
+    }
+
+    /// Why `value` is out of the range of the parameter, if it is.
+    fn check(&self, value: usize) -> Result<(), String> {
+        if (self.min..=self.max).contains(&value) {
+            return Ok(());
+        }
+        if !self.names.is_empty() {
+            return Err(format!("`{}` must be {}, found {}", self.name, self.expected(), value));
+        }
+        let range = match (self.min, self.max) {
+            (min, usize::MAX) => format!("at least {}", min),
+            (0, max) => format!("at most {}", max),
+            (min, max) => format!("between {} and {}", min, max),
+        };
+        Err(format!("`{}` must be {}, found {}", self.name, range, value))
+    }
+}
+
+/// The values of the parameters a day declares, starting out at their
+/// defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    declared: &'static [Param],
+    values: BTreeMap<&'static str, usize>,
+}
+
+impl Params {
+    pub fn new(declared: &'static [Param]) -> Self {
+        Params {
+            declared,
+            values: declared.iter().map(|param| (param.name, param.default)).collect(),
+        }
+    }
+
+    /// The parameters for `input`: the defaults, overridden by the values in
+    /// the `.toml` file next to it when there is one.
+    pub fn for_input(declared: &'static [Param], input: &str) -> Result<Self, InputError> {
+        let mut params = Params::new(declared);
+        let filename = params_filename(input);
+        if Path::new(&filename).exists() {
+            params.read_toml(&filename)?;
+        }
+        Ok(params)
+    }
+
+    /// The value of a declared parameter. Asking for one that was not declared
+    /// is a bug in the day, so that panics.
+    pub fn get(&self, name: &str) -> usize {
+        *self.values.get(name).unwrap_or_else(|| panic!("There is no parameter `{}`", name))
+    }
+
+    pub fn set(&mut self, name: &str, value: usize) -> Result<(), InputError> {
+        let param = self.param(name)?;
+        param.check(value).map_err(InputError::Params)?;
+        self.values.insert(param.name, value);
+        Ok(())
+    }
+
+    /// Applies a `name=value` assignment, as given on the command line. The
+    /// value is a number, or a name for a parameter that has them.
+    pub fn assign(&mut self, assignment: &str) -> Result<(), InputError> {
+        let (name, value) = assignment.split_once('=')
+            .ok_or_else(|| InputError::Params(format!("expected `name=value`, found `{}`", assignment)))?;
+        let value = self.param(name.trim())?.parse(value.trim()).map_err(InputError::Params)?;
+        self.set(name.trim(), value)
+    }
+
+    fn param(&self, name: &str) -> Result<&'static Param, InputError> {
+        self.declared
+            .iter()
+            .find(|param| param.name == name)
+            .ok_or_else(|| InputError::Params(self.unknown(name)))
+    }
+
+    /// Applies the `name = value` pairs of a TOML file.
+    pub fn read_toml(&mut self, filename: &str) -> Result<(), InputError> {
+        let content = fs::read_to_string(filename)
+            .map_err(|error| InputError::Io { file: filename.to_string(), error })?;
+        let table: toml::Table = toml::from_str(&content)
+            .map_err(|error| InputError::Params(format!("{}: {}", filename, error.message())))?;
+
+        for (name, value) in table {
+            self.set_toml(&name, &value).map_err(|error| InputError::Params(format!("{}: {}", filename, error)))?;
+        }
+        Ok(())
+    }
+
+    fn set_toml(&mut self, name: &str, value: &toml::Value) -> Result<(), InputError> {
+        let param = self.param(name)?;
+        let value = match value {
+            toml::Value::String(text) => param.parse(text),
+            _ => value.as_integer()
+                      .and_then(|value| usize::try_from(value).ok())
+                      .ok_or_else(|| format!("`{}` must be {}, found `{}`", name, param.expected(), value)),
+        };
+        self.set(name, value.map_err(InputError::Params)?)
+    }
+
+    fn unknown(&self, name: &str) -> String {
+        if self.declared.is_empty() {
+            format!("there is no parameter `{}`, this day has none", name)
+        } else {
+            let names: Vec<&str> = self.declared.iter().map(|param| param.name).collect();
+            format!("there is no parameter `{}`, expected one of {}", name, names.join(", "))
+        }
+    }
+}
+
+/// The path of the file holding the parameters for `input`, which is the
+/// input's own path with `.toml` appended (`test.dat.toml`).
+pub fn params_filename(input: &str) -> String {
+    format!("{}.toml", input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new("size", 70, "largest coordinate"),
+        Param::new("bytes", 1024, "bytes fallen for part 1"),
+    ];
+
+    #[test]
+    fn assignments_override_the_defaults() {
+        let mut params = Params::new(PARAMS);
+        params.assign("size=6").unwrap();
+        assert_eq!((params.get("size"), params.get("bytes")), (6, 1024));
+        assert_eq!(params.assign("width=6").unwrap_err().to_string(),
+                   "there is no parameter `width`, expected one of size, bytes");
+        assert!(params.assign("size=-1").is_err());
+    }
+
+    #[test]
+    fn values_out_of_range_are_rejected() {
+        const ROUNDS: &[Param] = &[Param::new("rounds", 3, "rounds to play").at_least(1).at_most(5)];
+        let mut params = Params::new(ROUNDS);
+        assert_eq!(params.assign("rounds=0").unwrap_err().to_string(),
+                   "`rounds` must be between 1 and 5, found 0");
+        assert!(params.assign("rounds=6").is_err());
+        params.assign("rounds=5").unwrap();
+        assert_eq!(params.get("rounds"), 5);
+    }
+
+    #[test]
+    fn named_values_are_set_by_name_or_index() {
+        const MODES: &[Param] = &[Param::new("mode", 0, "how to play").named(&["fast", "slow"])];
+        let mut params = Params::new(MODES);
+        params.assign("mode=slow").unwrap();
+        assert_eq!(params.get("mode"), 1);
+        params.assign("mode=0").unwrap();
+        assert_eq!(params.get("mode"), 0);
+        assert_eq!(params.assign("mode=2").unwrap_err().to_string(),
+                   "`mode` must be one of fast, slow, found 2");
+        assert_eq!(params.assign("mode=quick").unwrap_err().to_string(),
+                   "`mode` must be one of fast, slow, found `quick`");
+    }
+}
@@ -0,0 +1,229 @@
+pub mod stream;
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use common::{Answer, Line, Lines, ParseError, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    /// The columns of location ids, the puzzle compares the first two.
+    type Problem = Vec<Vec<usize>>;
+
+    fn name(&self) -> &'static str {
+        "day-01"
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem[0].clone(), problem[1].clone()).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(&problem[0], &problem[1]).into())
+    }
+}
+
+/// How two columns of location ids compare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    /// 0-based index of the left column.
+    pub left: usize,
+    /// 0-based index of the right column.
+    pub right: usize,
+    /// The total distance of part 1.
+    pub distance: usize,
+    /// The similarity score of part 2.
+    pub similarity: usize,
+    /// Whether `similarity` was estimated, in which case it is never lower
+    /// than the exact score.
+    pub estimated: bool,
+}
+
+/// Compares every column with every column to its right.
+pub fn compare(columns: &[Vec<usize>]) -> Vec<Comparison> {
+    pairs(columns.len()).map(|(left, right)| {
+                            Comparison {
+                                left,
+                                right,
+                                distance: solve1(columns[left].clone(), columns[right].clone()),
+                                similarity: solve2(&columns[left], &columns[right]),
+                                estimated: false,
+                            }
+                        })
+                        .collect()
+}
+
+/// Every pair of columns, the left one first.
+fn pairs(columns: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..columns).flat_map(move |left| (left + 1..columns).map(move |right| (left, right)))
+}
+
+fn solve1(mut first: Vec<usize>, mut second: Vec<usize>) -> usize {
+    first.sort();
+    second.sort();
+    let res: usize =
+        (0..first.len()).fold(0, |acc, i| acc + first[i].abs_diff(second[i]));
+
+    res
+}
+
+fn solve2(first: &[usize], second: &[usize]) -> usize {
+
+    let frequency_map = frequency_map(second);
+
+    let res: usize = first.iter()
+                          .map(|&value| value * frequency_map.get(&value).unwrap_or(&0))
+                          .sum();
+
+    res
+}
+
+/// How often every id occurs in `ids`.
+fn frequency_map(ids: &[usize]) -> HashMap<usize, usize> {
+    ids.iter()
+       .fold(HashMap::new(), |mut acc, &value| {
+           *acc.entry(value).or_insert(0) += 1;
+           acc
+       })
+}
+
+/// Two ids paired up by part 1, which pairs the smallest ids of both lists,
+/// then the next smallest, and so on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pairing {
+    /// 1-based position of the pair after sorting.
+    pub rank: usize,
+    pub left: usize,
+    pub right: usize,
+    pub distance: usize,
+    /// What the left id adds to the similarity score: itself times how often
+    /// it occurs in the right list.
+    pub similarity: usize,
+}
+
+/// What an id that occurs in both lists adds to the similarity score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contribution {
+    pub id: usize,
+    /// How often the id occurs in the left list.
+    pub occurrences: usize,
+    /// How often the id occurs in the right list, from the frequency map.
+    pub frequency: usize,
+    /// `id * occurrences * frequency`.
+    pub score: usize,
+}
+
+/// Why two lists are as far apart and as similar as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub distance: usize,
+    pub similarity: usize,
+    /// Every pairing, in the order of their rank.
+    pub pairings: Vec<Pairing>,
+    /// Every id with a share in the similarity score, the largest share first.
+    pub contributions: Vec<Contribution>,
+}
+
+impl Report {
+    /// The `k` pairings furthest apart, the furthest first. Ties keep their
+    /// rank order.
+    pub fn furthest(&self, k: usize) -> Vec<&Pairing> {
+        let mut pairings: Vec<&Pairing> = self.pairings.iter().collect();
+        pairings.sort_by_key(|pairing| std::cmp::Reverse(pairing.distance));
+        pairings.truncate(k);
+        pairings
+    }
+
+    /// Writes every pairing as a line of CSV, after a header.
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "rank,left,right,distance,similarity")?;
+        for pairing in &self.pairings {
+            writeln!(out, "{},{},{},{},{}",
+                     pairing.rank, pairing.left, pairing.right, pairing.distance, pairing.similarity)?;
+        }
+        Ok(())
+    }
+}
+
+/// Takes the totals of both parts apart, for the `left` and `right` lists.
+pub fn report(left: &[usize], right: &[usize]) -> Report {
+    let (mut sorted_left, mut sorted_right) = (left.to_vec(), right.to_vec());
+    sorted_left.sort();
+    sorted_right.sort();
+    let occurrences = frequency_map(left);
+    let frequency_map = frequency_map(right);
+
+    let pairings: Vec<Pairing> =
+        sorted_left.iter()
+                   .zip(&sorted_right)
+                   .enumerate()
+                   .map(|(index, (&left, &right))| Pairing {
+                       rank: index + 1,
+                       left,
+                       right,
+                       distance: left.abs_diff(right),
+                       similarity: left * frequency_map.get(&left).unwrap_or(&0),
+                   })
+                   .collect();
+
+    let mut contributions: Vec<Contribution> =
+        occurrences.into_iter()
+                   .filter_map(|(id, occurrences)| {
+                       let frequency = *frequency_map.get(&id)?;
+                       Some(Contribution { id, occurrences, frequency, score: id * occurrences * frequency })
+                   })
+                   .collect();
+    contributions.sort_by_key(|contribution| (std::cmp::Reverse(contribution.score), contribution.id));
+
+    Report {
+        distance: pairings.iter().map(|pairing| pairing.distance).sum(),
+        similarity: contributions.iter().map(|contribution| contribution.score).sum(),
+        pairings,
+        contributions,
+    }
+}
+
+/// The location ids on a line. Every line needs as many as `width`, and the
+/// first line sets it when it is `None`. There are always at least two.
+fn read_ids(line: &Line, width: Option<usize>) -> Result<Vec<usize>, ParseError> {
+    let ids = line.text
+                  .split_whitespace()
+                  .map(|word| line.parse::<usize>(word, "a location id"))
+                  .collect::<Result<Vec<usize>, ParseError>>()?;
+    match width {
+        None if ids.len() < 2 => Err(line.error(line.text.chars().count() + 1, "two location ids")),
+        Some(width) if ids.len() != width => {
+            let expected = format!("{} location ids", width);
+            match line.text.split_whitespace().nth(width) {
+                Some(extra) => Err(line.error_at(extra, expected)),
+                None => Err(line.error(line.text.chars().count() + 1, expected)),
+            }
+        }
+        _ => Ok(ids),
+    }
+}
+
+fn read_input(lines: Lines) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut columns: Vec<Vec<usize>> = Vec::new();
+
+    for line in lines {
+        let ids = read_ids(&line, columns.first().map(|_| columns.len()))?;
+        if columns.is_empty() {
+            columns = vec![Vec::new(); ids.len()];
+        }
+        columns.iter_mut().zip(ids).for_each(|(column, id)| column.push(id));
+    }
+
+    if columns.is_empty() {
+        columns = vec![Vec::new(); 2];
+    }
+    Ok(columns)
+}
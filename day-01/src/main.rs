@@ -1,53 +1,182 @@
-use std::fs::File;
-use std::io::{self, BufReader, BufRead};
 use std::env;
-use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand};
+use common::{SolveArgs, Solution};
+use day_01::stream::{compare_file, Frequencies, StreamOptions};
+use day_01::{report, Day01, Report};
 
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    let input = &args[1];
+#[derive(Parser)]
+#[command(about = "Solves day 1, or looks closer at its lists of location ids",
+          after_help = "An input named like a command is solved with `day-01 -- report`",
+          args_conflicts_with_subcommands = true,
+          arg_required_else_help = true)]
+struct Cli {
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
 
-    let input = read_input(input)?;
-    solve1(input.0.clone(), input.1.clone());
-    solve2(input.0, input.1);
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
 
-    Ok(())
+#[derive(Subcommand)]
+enum Mode {
+    /// Compares every column of location ids in the input with every column
+    /// to its right, without holding the lists in memory
+    Compare {
+        /// Location ids kept in memory at once
+        #[arg(long, default_value_t = StreamOptions::default().memory, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        memory: usize,
+
+        /// Estimates the similarity scores from count-min sketches of this
+        /// width and depth, e.g. `--count-min 4096x4`
+        #[arg(long, value_name = "WIDTHxDEPTH", value_parser = count_min)]
+        count_min: Option<Frequencies>,
+
+        /// Where the sorted runs are written
+        #[arg(long)]
+        temp_dir: Option<PathBuf>,
+
+        /// Location ids, `-` for stdin
+        input: String,
+    },
+    /// Shows which pairs of location ids in the input add the most to the
+    /// total distance, and which ids add the most to the similarity score
+    Report {
+        /// How many pairs and ids to show
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        /// The columns to compare, counting from 1
+        #[arg(long, value_name = "LEFT,RIGHT", default_value = "1,2", value_parser = columns)]
+        columns: (usize, usize),
+
+        /// Also writes every pairing to this CSV file, `-` writes only the
+        /// CSV to stdout
+        #[arg(long)]
+        csv: Option<String>,
+
+        /// Location ids, `-` for stdin
+        input: String,
+    },
 }
 
-fn solve1(mut first: Vec<usize>, mut second: Vec<usize>) {
-    first.sort();
-    second.sort();
-    let res: usize =
-        (0..first.len()).fold(0, |acc, i| acc + first[i].abs_diff(second[i]));
+struct ReportOptions {
+    top: usize,
+    /// The columns to compare, counting from 0.
+    columns: (usize, usize),
+    csv: Option<String>,
+    input: String,
+}
 
-    println!("{} is the total distance between your lists", res);
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let cli: Cli = common::parse_args(&Day01, &args);
+    let Some(mode) = cli.mode else {
+        return common::solve(&Day01, &cli.solve.expect("clap asks for an input when there is no command"));
+    };
+    match mode {
+        Mode::Compare { memory, count_min, temp_dir, input } => {
+            let frequencies = count_min.unwrap_or(Frequencies::Exact);
+            compare(&input, &StreamOptions { memory, frequencies, temp_dir })
+        }
+        Mode::Report { top, columns, csv, input } => show_report(&ReportOptions { top, columns, csv, input }),
+    }
 }
 
-fn solve2(first: Vec<usize>, second: Vec<usize>) {
+/// Reads `--count-min`, a width and depth like `4096x4`.
+fn count_min(arg: &str) -> Result<Frequencies, String> {
+    let (width, depth) = arg.split_once('x').ok_or("expected <width>x<depth>")?;
+    let parse = |number: &str| number.parse::<usize>().map_err(|error| format!("{}: {}", number, error));
+    Ok(Frequencies::CountMin { width: parse(width)?, depth: parse(depth)? })
+}
 
-    let frequency_map =
-        second.iter()
-              .fold(HashMap::new(), |mut acc, &value| {
-            *acc.entry(value).or_insert(0) += 1;
-            acc
-        });
+/// Reads `--columns`, two different columns counting from 1, as columns
+/// counting from 0.
+fn columns(arg: &str) -> Result<(usize, usize), String> {
+    let (left, right) = arg.split_once(',').ok_or("expected <left>,<right>")?;
+    let parse = |column: &str| match column.trim().parse::<usize>() {
+        Ok(0) => Err("columns count from 1".to_string()),
+        Ok(column) => Ok(column - 1),
+        Err(error) => Err(format!("{}: {}", column, error)),
+    };
+    let (left, right) = (parse(left)?, parse(right)?);
+    if left == right {
+        return Err("the columns must differ".to_string());
+    }
+    Ok((left, right))
+}
 
-    let res: usize = first.iter()
-                          .map(|&value| value * frequency_map.get(&value).unwrap_or(&0))
-                          .sum();
+fn compare(input: &str, options: &StreamOptions) -> ExitCode {
+    match compare_file(input, options) {
+        Ok(comparisons) => {
+            for comparison in comparisons {
+                let at_most = if comparison.estimated { "at most " } else { "" };
+                println!("columns {} and {}: distance {}, similarity {}{}",
+                         comparison.left + 1, comparison.right + 1, comparison.distance, at_most, comparison.similarity);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-    println!("{} is their similarity score?", res);
+fn show_report(options: &ReportOptions) -> ExitCode {
+    match write_report(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn read_input(filename: &String) -> io::Result<(Vec<usize>, Vec<usize>)> {
-    let file_in = File::open(filename)?;
-    let (mut first, mut second) = (Vec::new(), Vec::new());
+fn write_report(options: &ReportOptions) -> Result<(), Box<dyn Error>> {
+    let columns = Day01.read_input(&options.input, &Day01.default_params())?;
+    let (left, right) = options.columns;
+    if right >= columns.len() || left >= columns.len() {
+        return Err(format!("{} has only {} columns", options.input, columns.len()).into());
+    }
+    let report = report(&columns[left], &columns[right]);
 
-    for line in BufReader::new(file_in).lines().map(|x| x.unwrap()) {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        first.push(words[0].parse::<usize>().unwrap());
-        second.push(words[1].parse::<usize>().unwrap());
+    match options.csv.as_deref() {
+        Some("-") => return Ok(report.write_csv(&mut io::stdout().lock())?),
+        Some(csv) => {
+            let mut out = BufWriter::new(File::create(csv).map_err(|error| format!("{}: {}", csv, error))?);
+            report.write_csv(&mut out)?;
+            out.flush()?;
+        }
+        None => {}
     }
+    print_report(&report, options);
+    Ok(())
+}
+
+fn print_report(report: &Report, options: &ReportOptions) {
+    let share = |part: usize, total: usize| if total == 0 { 0.0 } else { part as f64 * 100.0 / total as f64 };
 
-    Ok((first, second))
+    println!("columns {} and {}", options.columns.0 + 1, options.columns.1 + 1);
+    println!();
+    println!("distance {} over {} pairs, the furthest apart:", report.distance, report.pairings.len());
+    println!("{:>8} {:>10} {:>10} {:>10} {:>7}", "rank", "left", "right", "distance", "share");
+    for pairing in report.furthest(options.top) {
+        println!("{:>8} {:>10} {:>10} {:>10} {:>6.1}%",
+                 pairing.rank, pairing.left, pairing.right, pairing.distance, share(pairing.distance, report.distance));
+    }
+
+    println!();
+    println!("similarity {} from {} ids in both lists, the largest shares:", report.similarity, report.contributions.len());
+    println!("{:>10} {:>8} {:>10} {:>12} {:>7}", "id", "left", "frequency", "score", "share");
+    for contribution in report.contributions.iter().take(options.top) {
+        println!("{:>10} {:>8} {:>10} {:>12} {:>6.1}%",
+                 contribution.id, contribution.occurrences, contribution.frequency, contribution.score,
+                 share(contribution.score, report.similarity));
+    }
 }
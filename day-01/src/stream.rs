@@ -0,0 +1,393 @@
+//! Comparing lists of location ids too large to hold in memory.
+//!
+//! The ids of every column are sorted with an external merge sort: they are
+//! read in chunks that fit in memory, each chunk is sorted and written to a
+//! temporary file as a run, and runs are merged as they pile up and once
+//! more at the end, until every column is a single sorted file. Distances then pair the sorted files up id by id, and
+//! similarity scores join them on equal ids, so memory stays bounded by the
+//! chunk size however long the lists are.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::iter::Peekable;
+use std::path::PathBuf;
+use common::{stream_lines, InputError};
+use crate::{pairs, read_ids, Comparison};
+
+/// How many runs a column keeps at most, which bounds the number of open
+/// files: they are merged down as soon as there are that many.
+const FAN_IN: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// How many location ids are held in memory at once, over all columns.
+    pub memory: usize,
+    pub frequencies: Frequencies,
+    /// Where the temporary files go, the system's temporary directory when
+    /// `None`.
+    pub temp_dir: Option<PathBuf>,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions { memory: 1 << 20, frequencies: Frequencies::Exact, temp_dir: None }
+    }
+}
+
+/// How the similarity scores are found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequencies {
+    /// Joins the sorted columns on equal ids, which gives the exact scores.
+    Exact,
+    /// Estimates the scores from count-min sketches of `depth` rows of `width`
+    /// counters per column, filled while the input is read. This saves a pass
+    /// over two sorted columns per pair, but collisions in the sketches can
+    /// make a score come out too high. It never comes out too low.
+    CountMin { width: usize, depth: usize },
+}
+
+/// Compares every column of `filename`, or of standard input for `-`, with
+/// every column to its right, like [`crate::compare`] does in memory.
+pub fn compare_file(filename: &str, options: &StreamOptions) -> Result<Vec<Comparison>, InputError> {
+    let mut columns: Vec<Column> = Vec::new();
+    for line in stream_lines(filename)? {
+        let line = line?;
+        let ids = read_ids(&line, columns.first().map(|_| columns.len()))?;
+        if columns.is_empty() {
+            columns = (0..ids.len()).map(|_| Column::new(ids.len(), options)).collect();
+        }
+        for (column, id) in columns.iter_mut().zip(ids) {
+            column.push(id as u64).map_err(temporary)?;
+        }
+    }
+    if columns.is_empty() {
+        columns = (0..2).map(|_| Column::new(2, options)).collect();
+    }
+
+    let sketches: Vec<Option<Sketch>> = columns.iter_mut().map(|column| column.sketch.take()).collect();
+    let sorted: Vec<File> = columns.into_iter()
+                                   .map(Column::sort)
+                                   .collect::<io::Result<_>>()
+                                   .map_err(temporary)?;
+
+    pairs(sorted.len()).map(|(left, right)| {
+                           let distance = distance(&sorted[left], &sorted[right])?;
+                           let similarity = match (&sketches[left], &sketches[right]) {
+                               (Some(left), Some(right)) => left.similarity(right),
+                               _ => similarity(&sorted[left], &sorted[right])?,
+                           };
+                           let estimated = sketches[left].is_some();
+                           Ok(Comparison { left, right, distance, similarity, estimated })
+                       })
+                       .collect::<io::Result<_>>()
+                       .map_err(temporary)
+}
+
+fn temporary(error: io::Error) -> InputError {
+    InputError::Io { file: "<temporary file>".to_string(), error }
+}
+
+/// The ids of a column that were read so far: the sorted runs written to disk
+/// and the chunk that is still being filled.
+struct Column {
+    chunk: Vec<u64>,
+    capacity: usize,
+    /// Every run with how many rounds of merging made it, oldest first, so
+    /// the rounds never go up along the runs.
+    runs: Vec<(File, usize)>,
+    sketch: Option<Sketch>,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Column {
+    /// A column of an input with `width` of them, which share the memory.
+    fn new(width: usize, options: &StreamOptions) -> Self {
+        let capacity = (options.memory / width).max(1);
+        let sketch = match options.frequencies {
+            Frequencies::Exact => None,
+            Frequencies::CountMin { width, depth } => Some(Sketch::new(width, depth)),
+        };
+        Column { chunk: Vec::with_capacity(capacity), capacity, runs: Vec::new(), sketch, temp_dir: options.temp_dir.clone() }
+    }
+
+    fn push(&mut self, id: u64) -> io::Result<()> {
+        if let Some(sketch) = &mut self.sketch {
+            sketch.add(id);
+        }
+        self.chunk.push(id);
+        if self.chunk.len() == self.capacity {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.chunk.sort_unstable();
+        let run = write_run(self.temp_dir.as_ref(), self.chunk.drain(..))?;
+        self.runs.push((run, 0));
+        if self.runs.len() == FAN_IN {
+            self.merge_down()?;
+        }
+        Ok(())
+    }
+
+    /// Merges the newest runs, those of the fewest rounds that make up at
+    /// least two runs, into one. Like carrying in a counter, every id is
+    /// merged about once per power of `FAN_IN` in the length of the column.
+    fn merge_down(&mut self) -> io::Result<()> {
+        let end = self.runs.len();
+        let mut start = end - 1;
+        while start > 0 && (end - start < 2 || self.runs[start - 1].1 == self.runs[start].1) {
+            start -= 1;
+        }
+        let rounds = self.runs[start].1;
+        let group: Vec<File> = self.runs.drain(start..).map(|(run, _)| run).collect();
+        let merged = merge(&group, self.temp_dir.as_ref())?;
+        self.runs.push((merged, rounds + 1));
+        Ok(())
+    }
+
+    /// Merges the runs, of which there are fewer than `FAN_IN`, into a single
+    /// sorted file.
+    fn sort(mut self) -> io::Result<File> {
+        if !self.chunk.is_empty() || self.runs.is_empty() {
+            self.spill()?;
+        }
+        if self.runs.len() == 1 {
+            return Ok(self.runs.pop().unwrap().0);
+        }
+        let runs: Vec<File> = self.runs.into_iter().map(|(run, _)| run).collect();
+        merge(&runs, self.temp_dir.as_ref())
+    }
+}
+
+fn write_run(temp_dir: Option<&PathBuf>, ids: impl Iterator<Item = u64>) -> io::Result<File> {
+    let file = match temp_dir {
+        Some(temp_dir) => tempfile::tempfile_in(temp_dir)?,
+        None => tempfile::tempfile()?,
+    };
+    let mut writer = BufWriter::new(file);
+    for id in ids {
+        writer.write_all(&id.to_le_bytes())?;
+    }
+    writer.into_inner().map_err(|error| error.into_error())
+}
+
+fn merge(runs: &[File], temp_dir: Option<&PathBuf>) -> io::Result<File> {
+    let mut readers = runs.iter().map(ids).collect::<io::Result<Vec<_>>>()?;
+    let mut heap: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+    for (index, reader) in readers.iter_mut().enumerate() {
+        if let Some(id) = reader.next().transpose()? {
+            heap.push(Reverse((id, index)));
+        }
+    }
+
+    let mut error = None;
+    let merged = std::iter::from_fn(|| {
+        let Reverse((id, index)) = heap.pop()?;
+        match readers[index].next().transpose() {
+            Ok(Some(next)) => heap.push(Reverse((next, index))),
+            Ok(None) => {}
+            Err(next_error) => error = Some(next_error),
+        }
+        Some(id)
+    });
+    let file = write_run(temp_dir, merged)?;
+    error.map_or(Ok(file), Err)
+}
+
+/// The ids in a run, from the start.
+fn ids(file: &File) -> io::Result<Ids<'_>> {
+    let mut file = file;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(Ids { reader: BufReader::new(file) })
+}
+
+struct Ids<'a> {
+    reader: BufReader<&'a File>,
+}
+
+impl Iterator for Ids<'_> {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0u8; 8];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => Some(Ok(u64::from_le_bytes(bytes))),
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => None,
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+/// The total distance between two sorted columns.
+fn distance(left: &File, right: &File) -> io::Result<usize> {
+    ids(left)?.zip(ids(right)?)
+              .try_fold(0, |total, (left, right)| Ok(total + left?.abs_diff(right?) as usize))
+}
+
+/// The similarity score of two sorted columns: every id that occurs in both
+/// counts its value times how often it occurs on the left times how often
+/// on the right.
+fn similarity(left: &File, right: &File) -> io::Result<usize> {
+    let (mut lefts, mut rights) = (Groups::new(ids(left)?), Groups::new(ids(right)?));
+    let (mut left, mut right) = (lefts.next().transpose()?, rights.next().transpose()?);
+
+    let mut total = 0;
+    while let (Some((left_id, left_count)), Some((right_id, right_count))) = (left, right) {
+        if left_id <= right_id {
+            if left_id == right_id {
+                total += (left_id * left_count * right_count) as usize;
+            }
+            left = lefts.next().transpose()?;
+        }
+        if right_id <= left_id {
+            right = rights.next().transpose()?;
+        }
+    }
+    Ok(total)
+}
+
+/// The distinct ids of a sorted run, each with how often it occurs.
+struct Groups<'a> {
+    ids: Peekable<Ids<'a>>,
+}
+
+impl<'a> Groups<'a> {
+    fn new(ids: Ids<'a>) -> Self {
+        Groups { ids: ids.peekable() }
+    }
+}
+
+impl Iterator for Groups<'_> {
+    type Item = io::Result<(u64, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = match self.ids.next()? {
+            Ok(id) => id,
+            Err(error) => return Some(Err(error)),
+        };
+        let mut count = 1;
+        while let Some(Ok(next)) = self.ids.peek() {
+            if *next != id {
+                break;
+            }
+            self.ids.next();
+            count += 1;
+        }
+        Some(Ok((id, count)))
+    }
+}
+
+/// A count-min sketch of a column. Next to how often the ids in every bucket
+/// occur, it keeps the sum of those ids, so two sketches estimate a
+/// similarity score without ever seeing the ids again. The sums are wide
+/// enough for 2^64 ids of any value.
+struct Sketch {
+    width: usize,
+    counts: Vec<Vec<u64>>,
+    sums: Vec<Vec<u128>>,
+}
+
+impl Sketch {
+    fn new(width: usize, depth: usize) -> Self {
+        let width = width.max(1);
+        let depth = depth.max(1);
+        Sketch { width, counts: vec![vec![0; width]; depth], sums: vec![vec![0; width]; depth] }
+    }
+
+    fn bucket(&self, id: u64, row: usize) -> usize {
+        // SplitMix64, seeded differently for every row
+        let mut hash = id.wrapping_add((row as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        ((hash ^ (hash >> 31)) % self.width as u64) as usize
+    }
+
+    fn add(&mut self, id: u64) {
+        for row in 0..self.counts.len() {
+            let bucket = self.bucket(id, row);
+            self.counts[row][bucket] += 1;
+            self.sums[row][bucket] += u128::from(id);
+        }
+    }
+
+    /// Every row overestimates the score, as ids sharing a bucket are counted
+    /// as equal, so the lowest row is the best estimate.
+    fn similarity(&self, right: &Sketch) -> usize {
+        self.sums.iter()
+                 .zip(&right.counts)
+                 .map(|(sums, counts)| {
+                     sums.iter()
+                         .zip(counts)
+                         .fold(0u128, |total, (sum, count)| total.saturating_add(sum.saturating_mul(u128::from(*count))))
+                 })
+                 .min()
+                 .unwrap_or(0)
+                 .min(usize::MAX as u128) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use crate::compare;
+
+    /// Three columns of pseudo random ids from a small range, so ids repeat.
+    fn columns(rows: usize) -> Vec<Vec<usize>> {
+        let mut rng = StdRng::seed_from_u64(42);
+        (0..3).map(|_| (0..rows).map(|_| rng.random_range(0..500)).collect()).collect()
+    }
+
+    fn compare_streamed(columns: &[Vec<usize>], options: &StreamOptions) -> Vec<Comparison> {
+        let dir = tempfile::tempdir().unwrap();
+        let filename = dir.path().join("input.dat");
+        let text: String = (0..columns[0].len())
+            .map(|row| columns.iter().map(|column| column[row].to_string()).collect::<Vec<_>>().join("   ") + "\n")
+            .collect();
+        fs::write(&filename, text).unwrap();
+        compare_file(&filename.to_string_lossy(), options).unwrap()
+    }
+
+    #[test]
+    fn small_chunks_give_the_same_answers_as_memory() {
+        let columns = columns(3000);
+        // 10 ids per column and chunk give 300 runs, which are merged down while they are written
+        let options = StreamOptions { memory: 30, ..StreamOptions::default() };
+        assert_eq!(compare_streamed(&columns, &options), compare(&columns));
+    }
+
+    #[test]
+    fn columns_keep_fewer_runs_than_the_fan_in() {
+        let ids = &columns(10_000)[0];
+        let options = StreamOptions { memory: 2, ..StreamOptions::default() };
+        let mut column = Column::new(1, &options);
+        for id in ids {
+            column.push(*id as u64).unwrap();
+            assert!(column.runs.len() < FAN_IN);
+        }
+        // 5000 runs go through more than one round of merging
+        assert!(column.runs.iter().any(|(_, rounds)| *rounds > 1));
+
+        let mut sorted: Vec<u64> = ids.iter().map(|id| *id as u64).collect();
+        sorted.sort_unstable();
+        let merged = column.sort().unwrap();
+        assert_eq!(super::ids(&merged).unwrap().collect::<io::Result<Vec<_>>>().unwrap(), sorted);
+    }
+
+    #[test]
+    fn count_min_never_underestimates() {
+        let columns = columns(3000);
+        let options = StreamOptions { frequencies: Frequencies::CountMin { width: 64, depth: 4 }, ..StreamOptions::default() };
+        for (streamed, exact) in compare_streamed(&columns, &options).iter().zip(compare(&columns)) {
+            assert_eq!(streamed.distance, exact.distance);
+            assert!(streamed.estimated);
+            assert!(streamed.similarity >= exact.similarity);
+        }
+    }
+}
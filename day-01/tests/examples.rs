@@ -0,0 +1,28 @@
+use common::{Answer, Solution};
+use day_01::{report, Day01};
+
+common::example_tests!(day_01::Day01; test);
+
+#[test]
+fn embedded_example() {
+    let problem = Day01.read_str("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", &Day01.default_params()).unwrap();
+    assert_eq!(Day01.solve1(&problem), Some(Answer::Number(11)));
+    assert_eq!(Day01.solve2(&problem), Some(Answer::Number(31)));
+}
+
+#[test]
+fn report_takes_the_totals_apart() {
+    let report = report(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]);
+    assert_eq!((report.distance, report.similarity), (11, 31));
+
+    let furthest = report.furthest(1)[0];
+    assert_eq!((furthest.rank, furthest.left, furthest.right, furthest.distance), (6, 4, 9, 5));
+    let dominant = &report.contributions[0];
+    assert_eq!((dominant.id, dominant.occurrences, dominant.frequency, dominant.score), (3, 3, 3, 27));
+
+    let mut csv = Vec::new();
+    report.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 7);
+    assert_eq!(csv.lines().nth(3), Some("3,3,3,0,9"));
+}
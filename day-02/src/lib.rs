@@ -0,0 +1,351 @@
+use std::fmt;
+use common::{Answer, InputError, Lines, Param, Params, ParseError, Solution};
+
+pub struct Day02;
+
+const PARAMS: &[Param] = &[
+    Param::new("min_step", 1, "smallest difference allowed between adjacent levels"),
+    Param::new("max_step", 3, "largest difference allowed between adjacent levels"),
+    Param::new("monotonicity", 2, "required: levels all increase or all decrease, optional: they may also stay level, free: anything goes")
+        .named(&["free", "optional", "required"]),
+    Param::new("removals", 1, "levels the Problem Dampener may remove in part 2"),
+];
+
+impl Solution for Day02 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-02"
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn configure(&self, problem: &mut Self::Problem, params: &Params) -> Result<(), InputError> {
+        let monotonicity = match params.get("monotonicity") {
+            0 => Monotonicity::Free,
+            1 => Monotonicity::Optional,
+            2 => Monotonicity::Required,
+            value => unreachable!("`monotonicity` is checked to be 0, 1 or 2, found {}", value),
+        };
+        problem.rules = Rules { min_step: params.get("min_step"), max_step: params.get("max_step"), monotonicity };
+        problem.removals = params.get("removals");
+        Ok(())
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    problem.raports.iter().filter(|rapport| is_safe(rapport, &problem.rules)).count()
+}
+
+fn solve2(problem: &Problem) -> usize {
+    problem.raports.iter().filter(|rapport| removals(rapport, &problem.rules, problem.removals).is_some()).count()
+}
+
+pub struct Problem {
+    pub raports: Vec<Vec<usize>>,
+    pub rules: Rules,
+    /// How many levels part 2 may remove from a report.
+    pub removals: usize,
+}
+
+/// What makes a report safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// The smallest difference allowed between adjacent levels.
+    pub min_step: usize,
+    /// The largest difference allowed between adjacent levels.
+    pub max_step: usize,
+    pub monotonicity: Monotonicity,
+}
+
+impl Default for Rules {
+    /// The rules of the puzzle: steps of 1 to 3, all in the same direction.
+    fn default() -> Self {
+        Rules { min_step: 1, max_step: 3, monotonicity: Monotonicity::Required }
+    }
+}
+
+/// Whether the levels of a report have to keep going the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monotonicity {
+    /// Either every level is higher than the one before, or every level is
+    /// lower.
+    Required,
+    /// The levels never turn back, but they may stay level.
+    Optional,
+    /// The levels may go up and down as they like.
+    Free,
+}
+
+/// Which way the levels of a report went so far, `None` while they did not
+/// go anywhere yet.
+type Trend = Option<std::cmp::Ordering>;
+
+/// All trends, in the order of their index in the removal table.
+const TRENDS: [Trend; 3] = [None, Some(std::cmp::Ordering::Less), Some(std::cmp::Ordering::Greater)];
+
+/// The fewest removals for a safe report ending in some level, and the level
+/// and trend it kept before that one.
+type Entry = Option<(usize, Option<(usize, usize)>)>;
+
+fn trend_index(trend: Trend) -> usize {
+    TRENDS.iter().position(|known| *known == trend).unwrap()
+}
+
+/// Why a report is unsafe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// Two adjacent levels are the same, while the levels have to keep going
+    /// up or down.
+    Equal,
+    /// The levels turn back after going the other way.
+    DirectionFlip,
+    /// Two adjacent levels are closer than the smallest step allows.
+    StepTooSmall,
+    /// Two adjacent levels are further apart than the largest step allows.
+    StepTooLarge,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Violation::Equal => "equal neighbours",
+            Violation::DirectionFlip => "direction flip",
+            Violation::StepTooSmall => "step too small",
+            Violation::StepTooLarge => "step too large",
+        };
+        f.write_str(description)
+    }
+}
+
+/// The first place where a report breaks the rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The index of the level that breaks the rules, the step into it from
+    /// the level before is the violation.
+    pub index: usize,
+    pub violation: Violation,
+}
+
+impl Rules {
+    /// The trend after stepping from level `from` to level `to`, or how that
+    /// step breaks the rules.
+    fn step(&self, trend: Trend, from: usize, to: usize) -> Result<Trend, Violation> {
+        let difference = from.abs_diff(to);
+        let direction = from.cmp(&to);
+        if direction.is_eq() && (difference < self.min_step || self.monotonicity == Monotonicity::Required) {
+            return Err(Violation::Equal);
+        }
+        if difference < self.min_step {
+            return Err(Violation::StepTooSmall);
+        }
+        if difference > self.max_step {
+            return Err(Violation::StepTooLarge);
+        }
+        match self.monotonicity {
+            Monotonicity::Free => Ok(trend),
+            _ if direction.is_eq() => Ok(trend),
+            _ if trend.is_some_and(|trend| trend != direction) => Err(Violation::DirectionFlip),
+            _ => Ok(Some(direction)),
+        }
+    }
+}
+
+pub fn is_safe(rapport: &[usize], rules: &Rules) -> bool {
+    diagnose(rapport, rules).is_none()
+}
+
+/// Where and how `rapport` first breaks the rules, `None` when it is safe.
+pub fn diagnose(rapport: &[usize], rules: &Rules) -> Option<Diagnosis> {
+    let mut trend = None;
+    for (index, levels) in rapport.windows(2).enumerate() {
+        match rules.step(trend, levels[0], levels[1]) {
+            Ok(next) => trend = next,
+            Err(violation) => return Some(Diagnosis { index: index + 1, violation }),
+        }
+    }
+    None
+}
+
+/// The fewest indices to remove from `rapport` to make it safe, `None` when
+/// that takes more than `k`. When several choices remove as few levels, any
+/// one of them is returned.
+///
+/// For every level and trend, the table holds the fewest removals that end a
+/// safe report at that level. A level can only follow one of the `k + 1`
+/// levels before it, so filling the table takes O(n·k) steps.
+pub fn removals(rapport: &[usize], rules: &Rules, k: usize) -> Option<Vec<usize>> {
+    let n = rapport.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    // best[i][trend] is the fewest removals for a safe report ending in level i
+    let mut best: Vec<[Entry; 3]> = vec![[None; 3]; n];
+    for (i, level) in rapport.iter().enumerate() {
+        // Starting at level i removes everything before it
+        if i <= k {
+            best[i][trend_index(None)] = Some((i, None));
+        }
+        for j in i.saturating_sub(k + 1)..i {
+            for (trend_at_j, entry) in best[j].into_iter().enumerate() {
+                let Some((removed, _)) = entry else {
+                    continue;
+                };
+                let removed = removed + (i - j - 1);
+                let Ok(trend) = rules.step(TRENDS[trend_at_j], rapport[j], *level) else {
+                    continue;
+                };
+                let slot = &mut best[i][trend_index(trend)];
+                if removed <= k && slot.is_none_or(|(known, _)| removed < known) {
+                    *slot = Some((removed, Some((j, trend_at_j))));
+                }
+            }
+        }
+    }
+
+    // Ending at level i removes everything after it
+    let (removed, last, trend) = (n.saturating_sub(k + 1)..n)
+        .flat_map(|i| (0..TRENDS.len()).map(move |trend| (i, trend)))
+        .filter_map(|(i, trend)| best[i][trend].map(|(removed, _)| (removed + n - 1 - i, i, trend)))
+        .filter(|(removed, _, _)| *removed <= k)
+        .min_by_key(|(removed, last, _)| (*removed, std::cmp::Reverse(*last)))?;
+
+    let mut kept = vec![false; n];
+    let mut at = Some((last, trend));
+    while let Some((i, trend)) = at {
+        kept[i] = true;
+        at = best[i][trend].unwrap().1;
+    }
+    let indices: Vec<usize> = (0..n).filter(|i| !kept[*i]).collect();
+    debug_assert_eq!(indices.len(), removed);
+    Some(indices)
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let rvalue = lines
+        .map(|line| {
+            line.text
+                .split_whitespace()
+                .map(|x| line.parse::<usize>(x, "a level"))
+                .collect::<Result<Vec<usize>, ParseError>>()
+        })
+        .collect::<Result<Vec<Vec<usize>>, ParseError>>()?;
+    // The rules and the number of removals are set by `configure`
+    Ok(Problem { raports: rvalue, rules: Rules::default(), removals: 0 })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn is_safe_with_a_single_removal(rapport: &[usize]) -> bool {
+        removals(rapport, &Rules::default(), 1).is_some()
+    }
+
+    #[test]
+    fn is_safe_accepts_gradual_monotone_reports() {
+        assert!(is_safe(&[7, 6, 4, 2, 1], &Rules::default()));
+        assert!(is_safe(&[1, 3, 6, 7, 9], &Rules::default()));
+    }
+
+    #[test]
+    fn is_safe_rejects_large_steps_direction_changes_and_plateaus() {
+        assert!(!is_safe(&[1, 2, 7, 8, 9], &Rules::default()));
+        assert!(!is_safe(&[9, 7, 6, 2, 1], &Rules::default()));
+        assert!(!is_safe(&[1, 3, 2, 4, 5], &Rules::default()));
+        assert!(!is_safe(&[8, 6, 4, 4, 1], &Rules::default()));
+    }
+
+    #[test]
+    fn a_single_removal_can_make_a_report_safe() {
+        assert!(is_safe_with_a_single_removal(&[1, 3, 2, 4, 5]));
+        assert!(is_safe_with_a_single_removal(&[8, 6, 4, 4, 1]));
+        assert!(!is_safe_with_a_single_removal(&[1, 2, 7, 8, 9]));
+    }
+
+    #[test]
+    fn removals_name_the_levels_to_drop() {
+        let rules = Rules::default();
+        assert_eq!(removals(&[7, 6, 4, 2, 1], &rules, 1), Some(vec![]));
+        assert_eq!(removals(&[1, 2, 9, 3, 4], &rules, 1), Some(vec![2]));
+        assert_eq!(removals(&[20, 1, 2, 3], &rules, 1), Some(vec![0]));
+        assert_eq!(removals(&[1, 2, 7, 8, 9], &rules, 1), None);
+        assert_eq!(removals(&[9, 1, 2, 3, 20, 4], &rules, 2), Some(vec![0, 4]));
+
+        let plateaus = Rules { min_step: 0, monotonicity: Monotonicity::Optional, ..rules };
+        assert_eq!(removals(&[8, 6, 4, 4, 1], &plateaus, 0), Some(vec![]));
+        let zigzag = Rules { monotonicity: Monotonicity::Free, ..rules };
+        assert_eq!(removals(&[1, 3, 2, 4, 5], &zigzag, 0), Some(vec![]));
+    }
+
+    #[test]
+    fn diagnose_names_the_first_violation() {
+        let rules = Rules::default();
+        let at = |index, violation| Some(Diagnosis { index, violation });
+        assert_eq!(diagnose(&[7, 6, 4, 2, 1], &rules), None);
+        assert_eq!(diagnose(&[1, 2, 7, 8, 9], &rules), at(2, Violation::StepTooLarge));
+        assert_eq!(diagnose(&[9, 7, 6, 2, 1], &rules), at(3, Violation::StepTooLarge));
+        assert_eq!(diagnose(&[1, 3, 2, 4, 5], &rules), at(2, Violation::DirectionFlip));
+        assert_eq!(diagnose(&[8, 6, 4, 4, 1], &rules), at(3, Violation::Equal));
+
+        let wide = Rules { min_step: 2, max_step: 5, ..rules };
+        assert_eq!(diagnose(&[1, 3, 4], &wide), at(2, Violation::StepTooSmall));
+        let plateaus = Rules { min_step: 0, monotonicity: Monotonicity::Optional, ..rules };
+        assert_eq!(diagnose(&[8, 6, 6, 7], &plateaus), at(3, Violation::DirectionFlip));
+    }
+
+    /// Tries every way of removing up to `k` levels, fewest first.
+    fn brute_force(rapport: &[usize], rules: &Rules, k: usize) -> Option<usize> {
+        (0..=k.min(rapport.len())).find(|&count| {
+            (0..1usize << rapport.len()).filter(|mask| mask.count_ones() as usize == count)
+                                        .any(|mask| {
+                                            let kept: Vec<usize> = (0..rapport.len()).filter(|i| mask & (1 << i) == 0)
+                                                                                     .map(|i| rapport[i])
+                                                                                     .collect();
+                                            kept.is_empty() || removals(&kept, rules, 0).is_some()
+                                        })
+        })
+    }
+
+    #[test]
+    fn removals_are_as_few_as_possible() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let all_rules = [
+            Rules::default(),
+            Rules { min_step: 0, max_step: 2, monotonicity: Monotonicity::Optional },
+            Rules { min_step: 1, max_step: 4, monotonicity: Monotonicity::Free },
+        ];
+        for _ in 0..300 {
+            let rapport: Vec<usize> = (0..rng.random_range(1..=8)).map(|_| rng.random_range(0..12)).collect();
+            for rules in &all_rules {
+                for k in 0..3 {
+                    assert_eq!(diagnose(&rapport, rules).is_none(), removals(&rapport, rules, 0).is_some());
+                    let found = removals(&rapport, rules, k);
+                    assert_eq!(found.as_ref().map(Vec::len), brute_force(&rapport, rules, k), "{:?} {:?} {}", rapport, rules, k);
+                }
+            }
+        }
+    }
+}
@@ -1,57 +1,195 @@
-use std::fs::File;
-use std::io::{self, BufReader, BufRead};
 use std::env;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use common::{SolveArgs, Solution};
+use crossterm::style::{StyledContent, Stylize};
+use day_02::{diagnose, removals, Day02, Diagnosis, Problem, Violation};
 
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    let input = &args[1];
+#[derive(Parser)]
+#[command(about = "Solves day 2, or shows why its reports are safe or not",
+          after_help = "An input named like a command is solved with `day-02 -- diagnose`",
+          args_conflicts_with_subcommands = true,
+          arg_required_else_help = true)]
+struct Cli {
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
+}
+
+#[derive(Subcommand)]
+enum Mode {
+    /// Shows, for every report in the input, which levels to remove to make
+    /// it safe, counting from 0. The `removals` parameter caps how many
+    /// levels may go
+    Removals(ReportArgs),
+    /// Shows, for every report in the input, where it first breaks the rules
+    /// and how, then how often every kind of violation turned up
+    Diagnose(ReportArgs),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum When {
+    /// When stdout is a terminal
+    Auto,
+    /// Even when stdout is a file or a pipe
+    Always,
+    /// Plain text only
+    Never,
+}
+
+/// What `day-02 removals` and `day-02 diagnose` show.
+#[derive(Args)]
+struct ReportArgs {
+    #[command(flatten)]
+    solve: SolveArgs,
+
+    /// Whether to colour the levels and verdicts
+    #[arg(long, alias = "color", value_enum, default_value_t = When::Auto)]
+    colour: When,
 
-    let input = read_input(input)?;
-    solve1(&input);
-    solve2(&input);
+    /// Leaves out the reports that are safe as they are
+    #[arg(long = "unsafe")]
+    only_unsafe: bool,
+}
+
+const VIOLATIONS: [Violation; 4] = [Violation::Equal, Violation::DirectionFlip, Violation::StepTooSmall, Violation::StepTooLarge];
 
-    Ok(())
+struct ReportOptions {
+    solve: SolveArgs,
+    colour: bool,
+    only_unsafe: bool,
 }
 
-fn solve1(raports: &Vec<Vec<usize>> ) {
-    let res: usize = raports.iter().filter(|rapport| is_safe(rapport)).count();
-    println!("{} reports are safe", res);
+impl From<ReportArgs> for ReportOptions {
+    fn from(args: ReportArgs) -> Self {
+        let colour = match args.colour {
+            When::Auto => io::stdout().is_terminal(),
+            When::Always => true,
+            When::Never => false,
+        };
+        ReportOptions { solve: args.solve, colour, only_unsafe: args.only_unsafe }
+    }
 }
 
-fn solve2(raports: &Vec<Vec<usize>> ) {
-    let res: usize = raports.iter().filter(|rapport| is_safe_with_a_single_removal(rapport)).count();
-    println!("{} reports are now safe", res);
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let cli: Cli = common::parse_args(&Day02, &args);
+    let Some(mode) = cli.mode else {
+        return common::solve(&Day02, &cli.solve.expect("clap asks for an input when there is no command"));
+    };
+    match mode {
+        Mode::Removals(args) => show(&args.into(), print_removals),
+        Mode::Diagnose(args) => show(&args.into(), print_diagnoses),
+    }
 }
 
-fn is_safe(rapport: &Vec<usize>) -> bool {
-    (1..rapport.len()).all(|i| {
-        (rapport[i - 1] != rapport[i])
-            && ((rapport[i] < rapport[i - 1]) == (rapport[1] < rapport[0]))
-            && rapport[i].abs_diff(rapport[i - 1]) <= 3
-    })
+fn show(options: &ReportOptions, print: fn(&Problem, &ReportOptions)) -> ExitCode {
+    match read_problem(options) {
+        Ok(problem) => {
+            print(&problem, options);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn is_safe_with_a_single_removal(rapport: &Vec<usize>) -> bool {
-    is_safe(rapport)
-        || (0..rapport.len()).any(|i| is_safe(&vector_from_vector_with_missing_index(rapport,
-                                                                                            i)))
+fn read_problem(options: &ReportOptions) -> Result<Problem, Box<dyn Error>> {
+    let solve = &options.solve;
+    let params = common::resolve_params(&Day02, &solve.input, solve.params.as_deref(), &solve.assignments)?;
+    Ok(Day02.read_input(&solve.input, &params)?)
 }
 
-fn vector_from_vector_with_missing_index(rapport: &Vec<usize>, index: usize) -> Vec<usize> {
-    let (left, right) = rapport.split_at(index);
-    [left, &right[1..]].concat()
+type Style = fn(String) -> StyledContent<String>;
+
+/// `text` styled by `style` when the output is coloured.
+fn paint(options: &ReportOptions, text: String, style: Style) -> StyledContent<String> {
+    if options.colour {
+        style(text)
+    } else {
+        text.stylize()
+    }
 }
 
-fn read_input(filename: &String) ->  io::Result<Vec<Vec<usize>>> {
-    let file_in = File::open(filename)?;
-    let rvalue: Vec<Vec<usize>> = BufReader::new(file_in)
-        .lines()
-        .map(|line| {
-            line.unwrap()
-                .split_whitespace()
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect()
-        })
-        .collect();
-    Ok(rvalue)
+fn print_removals(problem: &Problem, options: &ReportOptions) {
+    let mut safe = 0;
+    for (line, rapport) in problem.raports.iter().enumerate() {
+        let indices = removals(rapport, &problem.rules, problem.removals);
+        if indices.is_some() {
+            safe += 1;
+        }
+        if options.only_unsafe && indices.as_ref().is_some_and(Vec::is_empty) {
+            continue;
+        }
+
+        let levels: Vec<String> = rapport.iter()
+                                         .enumerate()
+                                         .map(|(i, level)| {
+                                             let style: Style = match &indices {
+                                                 Some(indices) if indices.contains(&i) => |text| text.red().crossed_out(),
+                                                 Some(_) => |text| text.green(),
+                                                 None => |text| text.red(),
+                                             };
+                                             paint(options, level.to_string(), style).to_string()
+                                         })
+                                         .collect();
+        let verdict = match indices {
+            Some(indices) if indices.is_empty() => paint(options, "safe".to_string(), |text| text.green()),
+            Some(indices) => {
+                let indices: Vec<String> = indices.iter().map(usize::to_string).collect();
+                paint(options, format!("remove {}", indices.join(", ")), |text| text.yellow())
+            }
+            None => paint(options, "unsafe".to_string(), |text| text.red().bold()),
+        };
+        println!("{:>5}: {}: {}", line + 1, levels.join(" "), verdict);
+    }
+    println!();
+    println!("{} of {} reports safe with at most {} levels removed", safe, problem.raports.len(), problem.removals);
+}
+
+fn print_diagnoses(problem: &Problem, options: &ReportOptions) {
+    let mut counts = [0; VIOLATIONS.len()];
+    for (line, rapport) in problem.raports.iter().enumerate() {
+        let diagnosis = diagnose(rapport, &problem.rules);
+        if let Some(Diagnosis { violation, .. }) = diagnosis {
+            counts[VIOLATIONS.iter().position(|known| *known == violation).unwrap()] += 1;
+        } else if options.only_unsafe {
+            continue;
+        }
+
+        // The step that breaks the rules is highlighted, the levels after it
+        // were never looked at
+        let levels: Vec<String> = rapport.iter()
+                                         .enumerate()
+                                         .map(|(i, level)| {
+                                             let style: Style = match diagnosis {
+                                                 None => |text| text.green(),
+                                                 Some(Diagnosis { index, .. }) if i + 1 == index || i == index => |text| text.red().bold(),
+                                                 Some(Diagnosis { index, .. }) if i > index => |text| text.dark_grey(),
+                                                 Some(_) => |text| text.stylize(),
+                                             };
+                                             paint(options, level.to_string(), style).to_string()
+                                         })
+                                         .collect();
+        let verdict = match diagnosis {
+            None => paint(options, "safe".to_string(), |text| text.green()),
+            Some(Diagnosis { index, violation }) => paint(options, format!("{} at {}", violation, index), |text| text.red()),
+        };
+        println!("{:>5}: {}: {}", line + 1, levels.join(" "), verdict);
+    }
+
+    let unsafe_reports: usize = counts.iter().sum();
+    println!();
+    println!("{} of {} reports safe", problem.raports.len() - unsafe_reports, problem.raports.len());
+    for (violation, count) in VIOLATIONS.iter().zip(counts) {
+        if count > 0 {
+            println!("{:>7} {}", count, violation);
+        }
+    }
 }
@@ -0,0 +1 @@
+common::example_tests!(day_02::Day02; test);
@@ -0,0 +1,400 @@
+//! A scanner and interpreter for corrupted memory: everything that is not a
+//! well-formed call of a known instruction is garbage and skipped.
+//!
+//! A call is the name of an instruction directly followed by its arguments
+//! in parentheses, separated by a comma and optional whitespace. Arguments
+//! are numbers, and for instructions that nest, also calls of instructions
+//! that compute a value.
+//!
+//! The memory can arrive in chunks, a call may start in one chunk and end in
+//! a later one. Line breaks are not part of the memory, so a call split over
+//! two lines still counts.
+//!
+//! A call is at most [`MAX_CALL`] bytes long, anything longer is garbage.
+//! That bounds how much of the memory has to be held on to while waiting for
+//! the end of a call, however the memory is corrupted.
+
+use std::collections::VecDeque;
+use std::io::{self, ErrorKind, Read};
+
+/// How many bytes [`Interpreter::feed_reader`] reads at once.
+const CHUNK: usize = 1 << 16;
+
+/// How long a call can be, in bytes, arguments and nested calls included.
+pub const MAX_CALL: usize = 1 << 10;
+
+/// What an instruction does when it runs.
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
+    /// Computes a value from the arguments, `None` when it does not fit in a
+    /// `usize`. The values of the calls that run add up to the result.
+    Value(fn(&[usize]) -> Option<usize>),
+    /// Turns the instructions that compute values on or off.
+    Enable(bool),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    pub name: &'static str,
+    pub arity: usize,
+    pub operation: Operation,
+    /// Whether the arguments may be calls as well as numbers.
+    pub nests: bool,
+}
+
+impl Instruction {
+    pub const fn new(name: &'static str, arity: usize, operation: Operation) -> Self {
+        Instruction { name, arity, operation, nests: false }
+    }
+
+    /// The same instruction, taking calls of the instructions that compute a
+    /// value as arguments, as in `mul(add(1,2),3)`.
+    pub const fn nesting(self) -> Self {
+        Instruction { nests: true, ..self }
+    }
+}
+
+pub const MUL: Instruction = Instruction::new("mul", 2, Operation::Value(|args| args[0].checked_mul(args[1])));
+pub const ADD: Instruction = Instruction::new("add", 2, Operation::Value(|args| args[0].checked_add(args[1])));
+pub const DO: Instruction = Instruction::new("do", 0, Operation::Enable(true));
+pub const DONT: Instruction = Instruction::new("don't", 0, Operation::Enable(false));
+
+/// The instructions part 1 knows, which only take numbers.
+pub const PART1: &[Instruction] = &[MUL];
+/// The instructions part 2 knows, which only take numbers.
+pub const PART2: &[Instruction] = &[MUL, DO, DONT];
+/// Every instruction there is, to pick from by name.
+pub const INSTRUCTIONS: &[Instruction] = &[MUL, ADD, DO, DONT];
+
+/// A well-formed call found in the input.
+#[derive(Debug, Clone, Copy)]
+pub struct Call<'a> {
+    /// Where the call starts, in bytes from the start of the input.
+    pub offset: usize,
+    /// The length of the call in bytes, including its arguments.
+    pub len: usize,
+    pub instruction: &'a Instruction,
+    /// The value it computes, `None` for instructions that enable or disable.
+    pub value: Option<usize>,
+}
+
+/// Why there is no call at some offset.
+#[derive(Debug, Clone, Copy)]
+enum Stop {
+    /// What is there is not a call.
+    Missing,
+    /// What is there could still become a call, but the input ran out.
+    Unfinished,
+}
+
+/// The calls in some input, in order, leaving out the garbage between them.
+pub struct Scanner<'a> {
+    input: &'a [u8],
+    position: usize,
+    instructions: &'a [Instruction],
+    /// Whether the input ends where `input` does, rather than continuing in
+    /// a chunk still to come.
+    complete: bool,
+    stopped: bool,
+    /// For every offset in the input, and the end of it, the call there.
+    calls: Vec<Found<'a>>,
+}
+
+/// A call of an instruction with the value it computes and the offset just
+/// past it, or why there is none.
+type Found<'a> = Result<(&'a Instruction, Option<usize>, usize), Stop>;
+
+impl<'a> Scanner<'a> {
+    /// Scans `input`, which is everything there is when `complete`, and
+    /// otherwise the start of something longer. A scanner of an incomplete
+    /// input stops at the first call it cannot finish.
+    ///
+    /// The calls at all offsets are found up front, from the end of the input
+    /// back. A call nested in an argument starts after the call around it,
+    /// so it has been found, or rejected, by the time the call around it
+    /// needs it, and no offset is looked at more than once.
+    pub fn new(input: &'a [u8], instructions: &'a [Instruction], complete: bool) -> Self {
+        let mut scanner = Scanner { input, position: 0, instructions, complete, stopped: false, calls: Vec::new() };
+        let mut calls = vec![Err(Stop::Missing); input.len() + 1];
+        for at in (0..=input.len()).rev() {
+            calls[at] = scanner.call(at, &calls);
+        }
+        scanner.calls = calls;
+        scanner
+    }
+
+    /// Where the scanner stopped: the input before it has been scanned,
+    /// the input from it on has to be scanned again with what follows.
+    pub fn rest(&self) -> usize {
+        self.position
+    }
+
+    /// The byte at `at`.
+    fn byte(&self, at: usize) -> Result<u8, Stop> {
+        match self.input.get(at) {
+            Some(byte) => Ok(*byte),
+            None if self.complete => Err(Stop::Missing),
+            None => Err(Stop::Unfinished),
+        }
+    }
+
+    /// Skips `expected` at `at`.
+    fn expect(&self, at: usize, expected: &[u8]) -> Result<usize, Stop> {
+        for (i, byte) in expected.iter().enumerate() {
+            if self.byte(at + i)? != *byte {
+                return Err(Stop::Missing);
+            }
+        }
+        Ok(at + expected.len())
+    }
+
+    /// The end of the run of bytes from `at` on that match `predicate`.
+    fn skip_while(&self, mut at: usize, predicate: impl Fn(u8) -> bool) -> Result<usize, Stop> {
+        while predicate(self.byte(at)?) {
+            at += 1;
+        }
+        Ok(at)
+    }
+
+    /// The call starting at `at`, with `calls` holding the calls at the
+    /// offsets after it. Calls longer than [`MAX_CALL`] are not calls, so
+    /// one that is unfinished that far from the end of the input is not one
+    /// either.
+    fn call(&self, at: usize, calls: &[Found<'a>]) -> Found<'a> {
+        match self.longest_call(at, calls) {
+            Ok((_, _, end)) if end - at > MAX_CALL => Err(Stop::Missing),
+            Err(Stop::Unfinished) if self.input.len() - at >= MAX_CALL => Err(Stop::Missing),
+            found => found,
+        }
+    }
+
+    /// The call starting at `at`, whatever its length. Calls of earlier
+    /// instructions in the table win.
+    fn longest_call(&self, at: usize, calls: &[Found<'a>]) -> Found<'a> {
+        for instruction in self.instructions {
+            let (arguments, end) = match self.arguments(at, instruction, calls) {
+                Ok(found) => found,
+                Err(Stop::Missing) => continue,
+                Err(Stop::Unfinished) => return Err(Stop::Unfinished),
+            };
+            match instruction.operation {
+                Operation::Value(compute) => {
+                    if let Some(value) = compute(&arguments) {
+                        return Ok((instruction, Some(value), end));
+                    }
+                }
+                Operation::Enable(_) => return Ok((instruction, None, end)),
+            }
+        }
+        Err(Stop::Missing)
+    }
+
+    /// The arguments of a call of `instruction` at `at`, with the offset just
+    /// past the closing parenthesis.
+    fn arguments(&self, at: usize, instruction: &Instruction, calls: &[Found<'a>]) -> Result<(Vec<usize>, usize), Stop> {
+        let mut position = self.expect(at, instruction.name.as_bytes())?;
+        position = self.expect(position, b"(")?;
+
+        let mut arguments = Vec::with_capacity(instruction.arity);
+        for i in 0..instruction.arity {
+            if i > 0 {
+                position = self.expect(position, b",")?;
+                position = self.skip_while(position, |byte| byte.is_ascii_whitespace())?;
+            }
+            let (argument, end) = self.argument(position, instruction.nests, calls)?;
+            arguments.push(argument);
+            position = end;
+        }
+
+        let end = self.expect(position, b")")?;
+        Ok((arguments, end))
+    }
+
+    /// A number at `at`, or the value of a nested call when `nests`, with the
+    /// offset just past it.
+    fn argument(&self, at: usize, nests: bool, calls: &[Found<'a>]) -> Result<(usize, usize), Stop> {
+        // More digits than a `usize` holds can not become a number, however
+        // many follow
+        let digits = self.input[at..].iter().take(21).take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 21 {
+            return Err(Stop::Missing);
+        }
+        if digits > 0 {
+            let end = self.skip_while(at, |byte| byte.is_ascii_digit())?;
+            // The digits are ASCII, so they are valid UTF-8
+            let number = std::str::from_utf8(&self.input[at..end]).unwrap().parse().map_err(|_| Stop::Missing)?;
+            return Ok((number, end));
+        }
+        if !nests {
+            return Err(Stop::Missing);
+        }
+        match calls[at]? {
+            (_, Some(value), end) => Ok((value, end)),
+            (_, None, _) => Err(Stop::Missing),
+        }
+    }
+}
+
+impl<'a> Iterator for Scanner<'a> {
+    type Item = Call<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.stopped && self.position < self.input.len() {
+            let offset = self.position;
+            match self.calls[offset] {
+                Ok((instruction, value, end)) => {
+                    self.position = end;
+                    return Some(Call { offset, len: end - offset, instruction, value });
+                }
+                Err(Stop::Missing) => self.position += 1,
+                Err(Stop::Unfinished) => self.stopped = true,
+            }
+        }
+        None
+    }
+}
+
+/// A call the interpreter came across.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event<'a> {
+    /// Where the call starts, in bytes from the start of the input, counting
+    /// the line breaks [`Interpreter::feed_reader`] left out of the memory.
+    pub offset: usize,
+    /// The call as it is written in the memory, without line breaks.
+    pub text: &'a str,
+    pub name: &'static str,
+    /// The value it computed, `None` for instructions that enable or disable.
+    pub value: Option<usize>,
+    /// Whether the call ran. Calls that compute a value are ignored while
+    /// the instructions are disabled.
+    pub executed: bool,
+}
+
+/// Runs the calls in memory that arrives in chunks. Whether the instructions
+/// are enabled carries over from one chunk to the next, and so does the
+/// start of a call a chunk ends in the middle of; that is all the memory it
+/// holds on to, at most [`MAX_CALL`] bytes.
+pub struct Interpreter<'a> {
+    instructions: &'a [Instruction],
+    enabled: bool,
+    total: usize,
+    /// The offset in the memory of the first byte in `pending`.
+    offset: usize,
+    /// The end of the memory so far, which may be the start of a call.
+    pending: Vec<u8>,
+    /// The line breaks left out after `offset`: the offset in the memory of
+    /// the byte after them, and how many bytes were left out there.
+    breaks: VecDeque<(usize, usize)>,
+    /// How many bytes were left out before `offset`.
+    skipped: usize,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Self {
+        Interpreter { instructions, enabled: true, total: 0, offset: 0, pending: Vec::new(), breaks: VecDeque::new(), skipped: 0 }
+    }
+
+    /// Runs the calls in the next chunk of memory, handing every one of them
+    /// to `trace`. A call the chunk ends in the middle of waits for the next
+    /// chunk.
+    pub fn feed(&mut self, chunk: &[u8], trace: impl FnMut(Event)) {
+        let mut buffer = std::mem::take(&mut self.pending);
+        if buffer.is_empty() {
+            let rest = self.scan(chunk, false, trace);
+            buffer.extend_from_slice(&chunk[rest..]);
+        } else {
+            buffer.extend_from_slice(chunk);
+            let rest = self.scan(&buffer, false, trace);
+            buffer.drain(..rest);
+        }
+        debug_assert!(buffer.len() < MAX_CALL, "only the start of a call is held on to");
+        self.pending = buffer;
+    }
+
+    /// Runs the calls in everything `reader` provides, leaving out the line
+    /// breaks.
+    pub fn feed_reader(&mut self, mut reader: impl Read, mut trace: impl FnMut(Event)) -> io::Result<()> {
+        let mut chunk = vec![0; CHUNK];
+        loop {
+            let read = match reader.read(&mut chunk) {
+                Ok(0) => return Ok(()),
+                Ok(read) => read,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            let mut memory = Vec::with_capacity(read);
+            for byte in &chunk[..read] {
+                if *byte != b'\n' && *byte != b'\r' {
+                    memory.push(*byte);
+                    continue;
+                }
+                let at = self.offset + self.pending.len() + memory.len();
+                match self.breaks.back_mut() {
+                    Some((last, count)) if *last == at => *count += 1,
+                    _ => self.breaks.push_back((at, 1)),
+                }
+            }
+            self.feed(&memory, &mut trace);
+        }
+    }
+
+    /// Runs the calls left at the end of the memory, when no more chunks
+    /// follow.
+    pub fn finish(&mut self, trace: impl FnMut(Event)) {
+        let buffer = std::mem::take(&mut self.pending);
+        self.scan(&buffer, true, trace);
+    }
+
+    /// Runs the calls in `buffer`, which starts at `self.offset`, and returns
+    /// how much of it was scanned.
+    fn scan(&mut self, buffer: &[u8], complete: bool, mut trace: impl FnMut(Event)) -> usize {
+        let mut scanner = Scanner::new(buffer, self.instructions, complete);
+        let mut breaks = self.breaks.iter().peekable();
+        let mut skipped = self.skipped;
+        for call in scanner.by_ref() {
+            let executed = match call.instruction.operation {
+                Operation::Value(_) => self.enabled,
+                Operation::Enable(enabled) => {
+                    self.enabled = enabled;
+                    true
+                }
+            };
+            if executed {
+                self.total += call.value.unwrap_or(0);
+            }
+            // A call is made of the name of an instruction and ASCII
+            let text = std::str::from_utf8(&buffer[call.offset..call.offset + call.len]).unwrap();
+            let offset = self.offset + call.offset;
+            while let Some((_, count)) = breaks.next_if(|(at, _)| *at <= offset) {
+                skipped += count;
+            }
+            trace(Event { offset: offset + skipped,
+                          text,
+                          name: call.instruction.name,
+                          value: call.value,
+                          executed });
+        }
+        let rest = scanner.rest();
+        self.offset += rest;
+        while let Some((_, count)) = self.breaks.pop_front_if(|(at, _)| *at <= self.offset) {
+            self.skipped += count;
+        }
+        rest
+    }
+
+    /// The sum of the values of the calls that ran so far.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+/// The sum of the values of the calls that run in the memory made of
+/// `chunks`.
+pub fn execute<'a>(chunks: impl IntoIterator<Item = &'a str>, instructions: &[Instruction]) -> usize {
+    let mut interpreter = Interpreter::new(instructions);
+    for chunk in chunks {
+        interpreter.feed(chunk.as_bytes(), |_| {});
+    }
+    interpreter.finish(|_| {});
+    interpreter.total()
+}
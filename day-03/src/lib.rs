@@ -0,0 +1,162 @@
+pub mod interpreter;
+
+use common::{Answer, Lines, ParseError, Solution};
+use interpreter::{execute, PART1, PART2};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Problem = Vec<String>;
+
+    fn name(&self) -> &'static str {
+        "day-03"
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+/// Both parts see the lines as one memory, a call may continue on the next
+/// line. Part 1 used to scan every line on its own, which only gives another
+/// answer for memories with a call split over two lines.
+fn solve1(input: &[String]) -> usize {
+    execute(input.iter().map(String::as_str), PART1)
+}
+
+fn solve2(input: &[String]) -> usize {
+    execute(input.iter().map(String::as_str), PART2)
+}
+
+
+fn read_input(lines: Lines) -> Result<Vec<String>, ParseError> {
+    Ok(lines.map(|line| line.text).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+    use super::interpreter::*;
+    use super::Day03;
+
+    /// Every instruction, nesting.
+    const NESTING: &[Instruction] = &[MUL.nesting(), ADD.nesting(), DO, DONT];
+
+    const EXAMPLE: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn the_interpreter_reports_every_call_it_came_across() {
+        let mut events = Vec::new();
+        let mut interpreter = Interpreter::new(PART2);
+        interpreter.feed(EXAMPLE.as_bytes(), |event| events.push((event.offset, event.name, event.executed, event.text.to_string())));
+        interpreter.finish(|event| events.push((event.offset, event.name, event.executed, event.text.to_string())));
+
+        let found: Vec<(usize, &str, bool)> = events.iter().map(|(offset, name, executed, _)| (*offset, *name, *executed)).collect();
+        assert_eq!(found, [(1, "mul", true), (20, "don't", true), (28, "mul", false), (48, "mul", false), (59, "do", true), (64, "mul", true)]);
+        assert_eq!(events[5].3, "mul(8,5)");
+        assert_eq!(interpreter.total(), 48);
+    }
+
+    #[test]
+    fn new_instructions_plug_into_the_table() {
+        assert_eq!(execute(["add(1,2)mul(add(2,3),mul(4,5))"], NESTING), 103);
+        assert_eq!(execute(["add(1,2)mul(add(2,3),mul(4,5))"], PART1), 20);
+        assert_eq!(execute(["add(1,2)mul(add(2,3),mul(4,5))"], &[MUL, ADD]), 28);
+        assert_eq!(execute(["don't()add(1,2)do()mul(do(),3)add(,1)add(1,2"], NESTING), 0);
+    }
+
+    #[test]
+    fn the_parts_only_take_numbers() {
+        assert_eq!(execute(["mul(mul(2,3),4)"], PART1), 6);
+        assert_eq!(execute(["mul(mul(2,3),4)"], PART2), 6);
+        assert_eq!(execute(["mul(mul(2,3),4)"], NESTING), 24);
+    }
+
+    #[test]
+    fn calls_carry_over_from_one_chunk_to_the_next() {
+        let memory = "mul(2,4)don't()mul(5,5)do()mul(add(1,2),  12345)add(1,2xmul(8,5)mul(1,2)";
+        let trace = |chunks: &[&[u8]]| {
+            let mut events = Vec::new();
+            let mut interpreter = Interpreter::new(NESTING);
+            for chunk in chunks {
+                interpreter.feed(chunk, |event| events.push((event.offset, event.text.to_string(), event.executed)));
+            }
+            interpreter.finish(|event| events.push((event.offset, event.text.to_string(), event.executed)));
+            (events, interpreter.total())
+        };
+
+        let whole = trace(&[memory.as_bytes()]);
+        assert_eq!(whole.1, 8 + 3 * 12345 + 40 + 2);
+        for split in 0..=memory.len() {
+            let (left, right) = memory.as_bytes().split_at(split);
+            assert_eq!(trace(&[left, right]), whole, "split at {}", split);
+        }
+        let bytes: Vec<&[u8]> = memory.as_bytes().chunks(1).collect();
+        assert_eq!(trace(&bytes), whole);
+    }
+
+    #[test]
+    fn unfinished_nested_calls_are_rejected_once() {
+        let memory = "mul(1,".repeat(20000) + "mul(2,3)";
+        assert_eq!(execute([memory.as_str()], NESTING), 6);
+        let nested = "mul(".repeat(100) + "1" + &",1)".repeat(100);
+        assert_eq!(execute([nested.as_str()], NESTING), 1);
+    }
+
+    #[test]
+    fn line_breaks_are_not_part_of_the_memory() {
+        let mut interpreter = Interpreter::new(PART2);
+        interpreter.feed_reader("xmul(2,4)don't\r\n()mul(5,5)\nm\nul(3,\n3)".as_bytes(), |_| {}).unwrap();
+        interpreter.finish(|_| {});
+        assert_eq!(interpreter.total(), 8);
+        assert_eq!(execute(["mu", "l(3,", "3)"], PART1), 9);
+    }
+
+    #[test]
+    fn part1_counts_calls_split_over_lines_like_part2() {
+        let problem = Day03.read_str("xmul(2,\n4)mul(3,3)\nmu\nl(1,1)\n", &Day03.default_params()).unwrap();
+        assert_eq!(Day03.solve1(&problem), Some(18.into()));
+        assert_eq!(Day03.solve1(&problem), Day03.solve2(&problem));
+    }
+
+    #[test]
+    fn offsets_count_the_line_breaks() {
+        let mut events = Vec::new();
+        let mut interpreter = Interpreter::new(PART2);
+        interpreter.feed_reader("mul(1,1)\r\n\nmu\nl(2,2)\ndo()".as_bytes(), |event| events.push((event.offset, event.text.to_string()))).unwrap();
+        interpreter.finish(|event| events.push((event.offset, event.text.to_string())));
+        assert_eq!(events, [(0, "mul(1,1)".to_string()), (11, "mul(2,2)".to_string()), (21, "do()".to_string())]);
+    }
+
+    #[test]
+    fn garbage_streams_through_in_bounded_memory() {
+        // Every call waits for the one in its second argument, which never
+        // finishes; only a call's worth of the memory may be held on to
+        let garbage = "mul(1,\n".repeat(1 << 20);
+        let memory = garbage.clone() + "mul(2,3)";
+        let mut events = Vec::new();
+        let mut interpreter = Interpreter::new(NESTING);
+        interpreter.feed_reader(memory.as_bytes(), |event| events.push(event.offset)).unwrap();
+        interpreter.finish(|event| events.push(event.offset));
+        assert_eq!(interpreter.total(), 6);
+        assert_eq!(events, [garbage.len()]);
+
+        // Of calls nested too deep, only the innermost ones that are short
+        // enough count
+        let nested = "mul(".repeat(MAX_CALL) + "2" + &",1)".repeat(MAX_CALL);
+        assert_eq!(execute([nested.as_str()], NESTING), 2);
+    }
+}
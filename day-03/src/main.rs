@@ -1,74 +1,116 @@
-use std::fs::File;
-use std::io::{self, BufReader, BufRead};
 use std::env;
-use regex::Regex;
-
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    let input = &args[1];
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process::ExitCode;
+use clap::{Parser, Subcommand};
+use common::SolveArgs;
+use day_03::interpreter::{Event, Instruction, Interpreter, INSTRUCTIONS};
+use day_03::Day03;
 
-    let input = read_input(input)?;
-    solve1(&input);
-    solve2(&input);
+#[derive(Parser)]
+#[command(about = "Solves day 3, or traces the instructions in its memory",
+          after_help = "An input named like a command is solved with `day-03 -- trace`",
+          args_conflicts_with_subcommands = true,
+          arg_required_else_help = true)]
+struct Cli {
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
 
-    Ok(())
+    #[command(subcommand)]
+    mode: Option<Mode>,
 }
 
-fn solve1(input: &Vec<String>) {
-    let res: usize = input.iter()
-                   .map(|input| find_multiplications_in_string_and_execute_them(input))
-                   .sum();
-    println!("{} is what you get if you add up all of the results of the multiplications", res)
-}
+#[derive(Subcommand)]
+enum Mode {
+    /// Runs the corrupted memory in the input and shows every instruction it
+    /// came across, with its offset in bytes in the input and whether it ran
+    /// or was ignored. Line breaks are not part of the memory, a call may
+    /// continue on the next line. The input is read in chunks, so it can be
+    /// larger than the memory of this machine
+    Trace {
+        /// The instructions to look for, out of mul, add, do and don't
+        #[arg(long, value_name = "NAME,...", value_delimiter = ',', default_value = "mul,do,don't", value_parser = instruction)]
+        instructions: Vec<Instruction>,
 
+        /// Lets the instructions take calls of those computing a value as
+        /// arguments, as in mul(add(1,2),3)
+        #[arg(long)]
+        nested: bool,
 
-fn find_multiplications_in_string_and_execute_them(input: &str) -> usize {
-    // find all patters of mul(\d+, \d+) in input
-    let re = Regex::new(r"mul\((\d+),\s*(\d+)\)").unwrap();
-    re.captures_iter(input)
-        .map(|cap| {
-            cap[1].parse::<usize>().unwrap() * cap[2].parse::<usize>().unwrap()
-        })
-        .sum()
-}
+        /// Only shows the totals
+        #[arg(long)]
+        quiet: bool,
 
-fn solve2(input: &Vec<String>) {
+        /// Corrupted memory, `-` for stdin
+        input: String,
+    },
+}
 
-    let combined_input = input.join("");
+struct TraceOptions {
+    instructions: Vec<Instruction>,
+    quiet: bool,
+    input: String,
+}
 
-    let res =
-        find_multiplications_in_string_and_execute_them_conditionally(combined_input.as_str());
-    println!("{} is what you get if you add up all of the results of just enabled multiplications",
-             res)
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let cli: Cli = common::parse_args(&Day03, &args);
+    let Some(mode) = cli.mode else {
+        return common::solve(&Day03, &cli.solve.expect("clap asks for an input when there is no command"));
+    };
+    let Mode::Trace { mut instructions, nested, quiet, input } = mode;
+    if nested {
+        instructions = instructions.into_iter().map(Instruction::nesting).collect();
+    }
+    trace(&TraceOptions { instructions, quiet, input })
 }
 
-fn find_multiplications_in_string_and_execute_them_conditionally(input: &str) -> usize {
-    let re = Regex::new(r"mul\((\d+),\s*(\d+)\)|do\(\)|don't\(\)").unwrap();
+/// Reads an instruction of `--instructions` by its name.
+fn instruction(name: &str) -> Result<Instruction, String> {
+    INSTRUCTIONS.iter()
+                .find(|instruction| instruction.name == name)
+                .copied()
+                .ok_or_else(|| format!("no instruction is called {}", name))
+}
 
-    re.captures_iter(input).fold((true, 0), |(enabled, answer), cap| {
-        if let Some(mul_match) = cap.get(1) {
-            // If "mul(x, y)" is matched and enabled is true
-            if enabled {
-                let x: usize = mul_match.as_str().parse().unwrap();
-                let y: usize = cap[2].parse().unwrap();
-                (enabled, answer + x * y)
-            } else {
-                (enabled, answer)
-            }
-        } else if cap.get(0).map_or(false, |m| m.as_str() == "do()") {
-            // If "do()" is matched, set enabled to true
-            (true, answer)
-        } else if cap.get(0).map_or(false, |m| m.as_str() == "don't()") {
-            // If "don't()" is matched, set enabled to false
-            (false, answer)
-        } else {
-            (enabled, answer)
+fn trace(options: &TraceOptions) -> ExitCode {
+    match print_trace(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
         }
-    }).1 // Return only the answer from the fold
+    }
 }
 
+fn print_trace(options: &TraceOptions) -> Result<(), Box<dyn Error>> {
+    let memory: Box<dyn Read> = if options.input == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(&options.input).map_err(|error| format!("{}: {}", options.input, error))?)
+    };
+
+    let (mut executed, mut ignored) = (0, 0);
+    let mut trace = |event: Event| {
+        if event.executed {
+            executed += 1;
+        } else {
+            ignored += 1;
+        }
+        if !options.quiet {
+            let verdict = if event.executed { "executed" } else { "ignored" };
+            let value = event.value.map(|value| format!(" = {}", value)).unwrap_or_default();
+            println!("{:>10} {:<8} {}{}", event.offset, verdict, event.text, value);
+        }
+    };
+    let mut interpreter = Interpreter::new(&options.instructions);
+    interpreter.feed_reader(memory, &mut trace).map_err(|error| format!("{}: {}", options.input, error))?;
+    interpreter.finish(&mut trace);
 
-fn read_input(filename: &String) ->  io::Result<Vec<String>> {
-    let file_in = File::open(filename)?;
-    Ok(BufReader::new(file_in).lines().map(|x| x.unwrap()).collect())
+    if !options.quiet {
+        println!();
+    }
+    println!("{} instructions executed, {} ignored, total {}", executed, ignored, interpreter.total());
+    Ok(())
 }
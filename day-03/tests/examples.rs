@@ -0,0 +1 @@
+common::example_tests!(day_03::Day03; test, test2);
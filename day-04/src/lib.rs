@@ -0,0 +1,158 @@
+pub mod pattern;
+pub mod planes;
+
+use common::{Answer, Lines, ParseError, Solution};
+use grid::Grid;
+use pattern::{Match, Search, Stencil, Turns};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-04"
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    let xmas = Search { stencils: vec![Stencil::word("XMAS").unwrap()], turns: Turns::Eighth, mirrors: false };
+    xmas.count(&problem.map)
+}
+
+fn solve2(problem: &Problem) -> usize {
+    let x_mas = Search { stencils: vec![Stencil::new(&["M.S", ".A.", "M.S"]).unwrap()], turns: Turns::Quarter, mirrors: false };
+    x_mas.count(&problem.map)
+}
+
+
+pub struct Problem {
+    map: Grid<char>,
+}
+
+impl Problem {
+    /// Every match of `search` in the word search.
+    pub fn search(&self, search: &Search) -> Vec<Match> {
+        search.run(&self.map)
+    }
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines.grid_rows("a letter", Some)?;
+    Ok(Problem { map: Grid::new(map) })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use grid::Point;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use super::*;
+    use super::pattern::Orientation;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::new(rows.iter().map(|row| row.chars().collect()).collect())
+    }
+
+    #[test]
+    fn words_are_found_in_every_direction_asked_for() {
+        let map = grid(&["XMAS", "MM..", "A.A.", "S..S"]);
+        let search = |turns| Search { stencils: vec![Stencil::word("XMAS").unwrap()], turns, mirrors: true };
+
+        let matches = search(Turns::Eighth).run(&map);
+        let found: Vec<(Point, u8)> = matches.iter().map(|found| (found.at, found.orientation.eighth_turns)).collect();
+        assert_eq!(found, [(Point::new(0, 0), 0), (Point::new(0, 0), 1), (Point::new(0, 0), 2)]);
+        assert_eq!(matches[1].points, [Point::new(0, 0), Point::new(1, 1), Point::new(2, 2), Point::new(3, 3)]);
+        assert_eq!(search(Turns::Quarter).count(&map), 2);
+        assert_eq!(search(Turns::None).count(&map), 1);
+    }
+
+    #[test]
+    fn symmetric_stencils_and_palindromes_match_once() {
+        let map = grid(&["M.S.", ".A..", "M.S.", "SAS."]);
+        let x = Search { stencils: vec![Stencil::new(&["M.S", ".A.", "M.S"]).unwrap()], turns: Turns::Quarter, mirrors: true };
+        let matches = x.run(&map);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].at, matches[0].orientation), (Point::new(0, 0), Orientation { eighth_turns: 0, mirrored: false }));
+
+        let sas = Search { stencils: vec![Stencil::word("SAS").unwrap()], turns: Turns::Eighth, mirrors: true };
+        assert_eq!(sas.count(&map), 1);
+    }
+
+    #[test]
+    fn stencils_can_be_mirrored() {
+        let map = grid(&["LL.", "LLL"]);
+        let l = |mirrors| Search { stencils: vec![Stencil::new(&["L..", "LLL"]).unwrap(), Stencil::new(&["L", "LL"]).unwrap()],
+                                   turns: Turns::None,
+                                   mirrors };
+        let stencils: Vec<usize> = l(false).run(&map).iter().map(|found| found.stencil).collect();
+        assert_eq!(stencils, [0, 1, 1]);
+        // Flipped top to bottom, the small L is found with its foot up as well
+        let matches = l(true).run(&map);
+        assert_eq!(matches.len(), 4);
+        assert!(matches[3].orientation.mirrored && matches[3].at == Point::new(0, 1));
+    }
+
+    #[test]
+    fn stencils_that_are_turns_of_one_another_are_all_found() {
+        let map = grid(&["XMAS", "...."]);
+        let words = Search { stencils: vec![Stencil::word("XMAS").unwrap(), Stencil::word("SAMX").unwrap()],
+                             turns: Turns::Eighth,
+                             mirrors: true };
+        let stencils: Vec<usize> = words.run(&map).iter().map(|found| found.stencil).collect();
+        assert_eq!(stencils, [0, 1]);
+
+        let corners = Search { stencils: vec![Stencil::new(&["A.", "BC"]).unwrap(), Stencil::new(&["CB", ".A"]).unwrap()],
+                               turns: Turns::Quarter,
+                               mirrors: false };
+        assert_eq!(corners.count(&grid(&["BA", "C."])), 2);
+    }
+
+    /// Counts with the bit planes, in bands of `band` rows.
+    fn count_planes(map: &Grid<char>, targets: &[planes::Target], band: usize) -> Vec<usize> {
+        let mut counter = planes::Counter::new(targets, &planes::Options { band });
+        for line in common::Lines::from_text("<string>", &map.to_string()) {
+            counter.push(&line).unwrap();
+        }
+        counter.finish()
+    }
+
+    #[test]
+    fn bit_planes_count_what_the_search_finds() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let targets = [planes::Target::word("XMAS").unwrap(), planes::Target::cross("MAS").unwrap(),
+                       planes::Target::word("SAS").unwrap(), planes::Target::cross("SAS").unwrap(), planes::Target::word("A").unwrap()];
+        let searches = [("XMAS", Turns::Eighth), ("M.S/.A./M.S", Turns::Quarter), ("SAS", Turns::Eighth), ("S.S/.A./S.S", Turns::Quarter), ("A", Turns::Eighth)];
+
+        for _ in 0..40 {
+            let (width, height) = (rng.random_range(1..=150), rng.random_range(1..=12));
+            let map = Grid::new((0..height).map(|_| (0..width).map(|_| ['X', 'M', 'A', 'S'][rng.random_range(0..4)]).collect()).collect());
+            let expected: Vec<usize> = searches.iter()
+                                               .map(|(rows, turns)| {
+                                                   let stencil = Stencil::new(&rows.split('/').collect::<Vec<&str>>()).unwrap();
+                                                   Search { stencils: vec![stencil], turns: *turns, mirrors: false }.count(&map)
+                                               })
+                                               .collect();
+            for band in [1, 2, 5, 64] {
+                assert_eq!(count_planes(&map, &targets, band), expected, "{}x{} in bands of {}", width, height, band);
+            }
+        }
+    }
+}
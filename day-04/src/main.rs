@@ -1,168 +1,174 @@
-use std::fs::File;
-use std::io::{self, BufReader, BufRead};
 use std::env;
-
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    let input = &args[1];
-
-    let problem = read_input(input)?;
-
-    solve1(&problem);
-    solve2(&problem);
-
-    Ok(())
-}
-
-fn solve1(problem: &Problem) {
-    let res: usize  =
-        problem.iter_points()
-               .map(|point| is_expected_char_on_point_on_map(problem, &point))
-               .sum();
-    println!("{} many times does XMAS appear", res);
+use std::error::Error;
+use std::process::ExitCode;
+use clap::builder::{PossibleValuesParser, RangedU64ValueParser, TypedValueParser};
+use clap::{ArgGroup, Parser, Subcommand};
+use common::{SolveArgs, Solution};
+use day_04::pattern::{Search, Stencil, Turns};
+use day_04::planes::{count_file, Options, Target};
+use day_04::Day04;
+
+#[derive(Parser)]
+#[command(about = "Solves day 4, or finds other words and stencils in its letters",
+          after_help = "An input named like a command is solved with `day-04 -- count`",
+          args_conflicts_with_subcommands = true,
+          arg_required_else_help = true)]
+struct Cli {
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
 }
 
-fn solve2(problem: &Problem) {
-    let res: usize  =
-        problem.iter_points()
-            .filter(|point| is_expected_x_char_on_point_on_map(problem, &point))
-            .count();
-    println!("{} many times does X-MAS appear", res);
+#[derive(Subcommand)]
+enum Mode {
+    /// Finds words and stencils in the letters of the input and shows where
+    /// they are, as the column and row of their first letter counting from 0
+    #[command(group = ArgGroup::new("targets").required(true).multiple(true))]
+    Find {
+        /// A word to look for
+        #[arg(long = "word", value_name = "WORD", group = "targets", value_parser = word)]
+        words: Vec<(String, Stencil)>,
+
+        /// A stencil to look for, its rows separated by `/` and `.` matching
+        /// any letter, like `M.S/.A./M.S`
+        #[arg(long = "stencil", value_name = "ROWS", group = "targets", value_parser = stencil)]
+        stencils: Vec<(String, Stencil)>,
+
+        /// Which turns of the stencils to look for, eighth turns find words
+        /// along the diagonals
+        #[arg(long, default_value = "eighth", value_parser = PossibleValuesParser::new(["none", "quarter", "eighth"]).map(|turns| match turns.as_str() {
+            "none" => Turns::None,
+            "quarter" => Turns::Quarter,
+            _ => Turns::Eighth,
+        }))]
+        turns: Turns,
+
+        /// Also looks for the stencils flipped
+        #[arg(long)]
+        mirrors: bool,
+
+        /// Only shows how many matches there are
+        #[arg(long)]
+        count: bool,
+
+        /// Letters, `-` for stdin
+        input: String,
+    },
+    /// Counts words and crosses in the letters of the input without holding
+    /// all of it in memory, for grids of many megabytes. Without targets it
+    /// counts what the puzzle asks for, XMAS and crosses of MAS
+    Count {
+        /// A word to count in all eight directions
+        #[arg(long = "word", value_name = "WORD", value_parser = word_target)]
+        words: Vec<(String, Target)>,
+
+        /// A word to count crossed with itself on the diagonals at its middle
+        /// letter
+        #[arg(long = "cross", value_name = "WORD", value_parser = cross_target)]
+        crosses: Vec<(String, Target)>,
+
+        /// How many rows a thread counts at once
+        #[arg(long, default_value_t = Options::default().band, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        band: usize,
+
+        /// Letters, `-` for stdin
+        input: String,
+    },
 }
 
-fn is_expected_char_on_point_on_map(problem: &Problem,
-                                    point: &Point) -> usize {
-
-    let expected_chars = vec!['X', 'M', 'A', 'S'];
-
-    let dxys = vec![Point::new(0,1),
-                               Point::new(0,-1),
-                               Point::new(-1,0),
-                               Point::new(1,0),
-                               Point::new(1,1),
-                               Point::new(-1,-1),
-                               Point::new(1,-1),
-                               Point::new(-1,1)];
-
-    dxys.iter().filter(|dxdy| { is_expected_char_on_point_on_map_h(problem,
-                                                                           point,
-                                                                           dxdy,
-                                                                         0,
-                                                                           &expected_chars)})
-              .count()
+struct FindOptions {
+    search: Search,
+    labels: Vec<String>,
+    count: bool,
+    input: String,
 }
 
-fn is_sam(chars: &Option<Vec<char>>) -> bool {
-    if let Some(chars) = chars {
-        let concatenated: String = chars.iter().collect();
-        concatenated == "SAM" || concatenated == "MAS"
-    } else {
-        false
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let cli: Cli = common::parse_args(&Day04, &args);
+    let Some(mode) = cli.mode else {
+        return common::solve(&Day04, &cli.solve.expect("clap asks for an input when there is no command"));
+    };
+    match mode {
+        Mode::Find { words, stencils, turns, mirrors, count, input } => {
+            let (labels, stencils) = words.into_iter().chain(stencils).unzip();
+            find(&FindOptions { search: Search { stencils, turns, mirrors }, labels, count, input })
+        }
+        Mode::Count { words, crosses, band, input } => {
+            let mut targets: Vec<(String, Target)> = words.into_iter().chain(crosses).collect();
+            if targets.is_empty() {
+                targets = vec![word_target("XMAS").unwrap(), cross_target("MAS").unwrap()];
+            }
+            count(&targets, &Options { band }, &input)
+        }
     }
 }
 
-fn is_expected_x_char_on_point_on_map(problem: &Problem,
-                                      point: &Point) -> bool {
-    let left_cross_bar: [&Point; 3] = [&point.add(&Point::new(-1, -1)),
-                                       point,
-                                       &point.add(&Point::new(1, 1))];
-
-    let left_sam = problem.get_all_chars_on_points(&left_cross_bar);
-
-    let right_cross_bar: [&Point; 3] = [&point.add(&Point::new(1, -1)),
-                                        point,
-                                        &point.add(&Point::new(-1, 1))];
-
-    let right_sam = problem.get_all_chars_on_points(&right_cross_bar);
-
-    is_sam(&left_sam) && is_sam(&right_sam)
+/// Reads a `--word` to find, labelled by itself.
+fn word(word: &str) -> Result<(String, Stencil), String> {
+    let stencil = Stencil::word(word).ok_or("a word needs a letter")?;
+    Ok((word.to_string(), stencil))
 }
 
-fn is_expected_char_on_point_on_map_h(problem: &Problem,
-                                      point: &Point,
-                                      dxdy: &Point,
-                                      i: usize,
-                                      expected_chars: &Vec<char>) -> bool {
-
-    i >= expected_chars.len()
-        ||  (problem.get_char_on_point(&point) == Some(expected_chars[i])
-                && is_expected_char_on_point_on_map_h(problem,
-                                                      &point.add(&dxdy),
-                                                      dxdy,
-                                                    i + 1,
-                                                      expected_chars))
+/// Reads a `--stencil` to find, labelled by its rows.
+fn stencil(rows: &str) -> Result<(String, Stencil), String> {
+    let stencil = Stencil::new(&rows.split('/').collect::<Vec<&str>>()).ok_or("a stencil needs a letter")?;
+    Ok((rows.to_string(), stencil))
 }
 
-
-struct Problem {
-    map: Vec<Vec<char>>,
+/// Reads a `--word` to count, labelled by itself.
+fn word_target(word: &str) -> Result<(String, Target), String> {
+    let target = Target::word(word).ok_or("a word needs a letter")?;
+    Ok((word.to_string(), target))
 }
 
-impl Problem {
-
-    fn width(&self) -> i32 {
-        self.map.first().unwrap().len() as i32
-    }
-
-    fn height(&self) -> i32 {
-        self.map.len() as i32
-    }
+/// Reads a `--cross` to count, labelled as crosses of the word.
+fn cross_target(word: &str) -> Result<(String, Target), String> {
+    let target = Target::cross(word).ok_or("a cross needs a word with a middle letter")?;
+    Ok((format!("crosses of {}", word), target))
+}
 
-    fn get_char_on_point(&self, point: &Point) -> Option<char> {
-        if self.is_on_map(point) {
-            Some(self.map[point.y as usize][point.x as usize])
-        } else {
-            None
+fn find(options: &FindOptions) -> ExitCode {
+    match print_matches(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
         }
     }
+}
 
-    fn get_all_chars_on_points(&self, points: &[&Point]) -> Option<Vec<char>> {
-        // Collect all characters from the given points
-        let chars: Vec<_> = points
-            .iter()
-            .map(|point| self.get_char_on_point(point))
-            .collect();
-
-        // Check if any point is out of bounds (None in the collected chars)
-        if chars.iter().any(|&ch| ch.is_none()) {
-            None
-        } else {
-            Some(chars.into_iter().map(|ch| ch.unwrap()).collect())
-        }
-    }
+fn print_matches(options: &FindOptions) -> Result<(), Box<dyn Error>> {
+    let problem = Day04.read_input(&options.input, &Day04.default_params())?;
+    let matches = problem.search(&options.search);
 
-    fn is_on_map(&self, point: &Point) -> bool {
-        point.x < self.width()  && point.x >= 0  && point.y >= 0 && point.y < self.height()
+    if !options.count {
+        for found in &matches {
+            println!("{:>5},{:<5} {}  {}", found.at.x, found.at.y, options.labels[found.stencil], found.orientation);
+        }
+        println!();
     }
-
-    fn iter_points(&self) -> impl Iterator<Item = Point> + '_ {
-        (0..self.height()).flat_map(move |y| {
-            (0..self.width()).map(move |x| Point::new(x, y))
-        })
+    for (stencil, label) in options.labels.iter().enumerate() {
+        println!("{} matches of {}", matches.iter().filter(|found| found.stencil == stencil).count(), label);
     }
+    Ok(())
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
-struct Point {
-    x: i32,
-    y: i32
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
-    }
-
-    fn add(&self, other: &Point) -> Self {
-        Point { x: self.x + other.x, y: self.y + other.y  }
+fn count(targets: &[(String, Target)], options: &Options, input: &str) -> ExitCode {
+    let labels: Vec<&String> = targets.iter().map(|(label, _)| label).collect();
+    let targets: Vec<Target> = targets.iter().map(|(_, target)| target.clone()).collect();
+    match count_file(input, &targets, options) {
+        Ok(counts) => {
+            for (label, count) in labels.iter().zip(counts) {
+                println!("{} {}", count, label);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
     }
 }
-
-fn read_input(filename: &String) -> io::Result<Problem> {
-    let file_in = File::open(filename)?;
-    let map = BufReader::new(file_in)
-        .lines()
-        .map(|line| line.unwrap().chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
-    Ok(Problem { map })
-}
\ No newline at end of file
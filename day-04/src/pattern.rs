@@ -0,0 +1,197 @@
+//! Searching a grid of letters for words and small stencils, turned and
+//! mirrored.
+
+use std::fmt;
+use grid::{Grid, Point};
+
+/// The letter in a stencil that matches any letter.
+pub const WILDCARD: char = '.';
+
+/// The directions a stencil's rows can point in, an eighth turn clockwise
+/// apart, starting to the right.
+const DIRECTIONS: [Point; 8] = [
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+    Point::new(0, -1),
+    Point::new(1, -1),
+];
+
+/// Letters at fixed offsets from one another. The offsets count from the
+/// first letter of the first row that has one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil {
+    cells: Vec<(Point, char)>,
+}
+
+impl Stencil {
+    /// A stencil made of the letters of `word`, left to right, `None` when it
+    /// has none.
+    pub fn word(word: &str) -> Option<Self> {
+        Self::new(&[word])
+    }
+
+    /// A stencil made of `rows`, top to bottom, in which [`WILDCARD`] and
+    /// spaces match any letter. `None` when it has no letters.
+    pub fn new(rows: &[&str]) -> Option<Self> {
+        let cells: Vec<(Point, char)> = rows.iter()
+                                            .enumerate()
+                                            .flat_map(|(y, row)| {
+                                                row.chars()
+                                                   .enumerate()
+                                                   .map(move |(x, letter)| (Point::new(x as i32, y as i32), letter))
+                                            })
+                                            .filter(|(_, letter)| *letter != WILDCARD && *letter != ' ')
+                                            .collect();
+        let origin = cells.first()?.0;
+        Some(Stencil { cells: cells.into_iter().map(|(point, letter)| (point.minus(&origin), letter)).collect() })
+    }
+
+    /// The letters of the stencil in `orientation`, relative to where its
+    /// first letter ends up.
+    fn oriented(&self, orientation: Orientation) -> Vec<(Point, char)> {
+        let along = DIRECTIONS[orientation.eighth_turns as usize % 8];
+        let mut across = DIRECTIONS[(orientation.eighth_turns as usize + 2) % 8];
+        if orientation.mirrored {
+            across = across.mul(-1);
+        }
+        self.cells.iter()
+                  .map(|(point, letter)| (along.mul(point.x).add(&across.mul(point.y)), *letter))
+                  .collect()
+    }
+}
+
+/// Which turns of a stencil to search for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turns {
+    /// Only the stencil as it is written.
+    None,
+    /// The stencil turned 0, 90, 180 and 270 degrees.
+    Quarter,
+    /// The stencil turned in steps of 45 degrees, which finds words along the
+    /// diagonals too. A stencil of more than one row turned an eighth turn
+    /// has its rows along the diagonals, and gaps between them.
+    Eighth,
+}
+
+impl Turns {
+    fn eighth_turns(self) -> impl Iterator<Item = u8> {
+        let step = match self {
+            Turns::None => 8,
+            Turns::Quarter => 2,
+            Turns::Eighth => 1,
+        };
+        (0..8).step_by(step)
+    }
+}
+
+/// How a stencil lay in the grid where it matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Orientation {
+    /// How far the stencil was turned clockwise, in eighths of a turn.
+    pub eighth_turns: u8,
+    /// Whether the stencil was flipped top to bottom before it was turned.
+    pub mirrored: bool,
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "turned {}°", self.eighth_turns as u32 * 45)?;
+        if self.mirrored {
+            write!(f, ", mirrored")?;
+        }
+        Ok(())
+    }
+}
+
+/// Which stencils to look for, and in which orientations.
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub stencils: Vec<Stencil>,
+    pub turns: Turns,
+    pub mirrors: bool,
+}
+
+/// A place where a stencil matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The index of the stencil in [`Search::stencils`].
+    pub stencil: usize,
+    /// Where the first letter of the stencil is.
+    pub at: Point,
+    pub orientation: Orientation,
+    /// Where all letters of the stencil are, in the order of the stencil.
+    pub points: Vec<Point>,
+}
+
+/// A stencil in one of the orientations searched for.
+struct Oriented {
+    stencil: usize,
+    orientation: Orientation,
+    /// The letters, relative to where the first one is.
+    cells: Vec<(Point, char)>,
+}
+
+impl Oriented {
+    fn matches(&self, map: &Grid<char>, at: &Point) -> bool {
+        self.cells.iter().all(|(offset, letter)| map.get(&at.add(offset)) == Some(letter))
+    }
+}
+
+impl Search {
+    /// Every stencil in every orientation, leaving out the orientations in
+    /// which a stencil covers the same letters as in an earlier orientation
+    /// of itself, so that a symmetric stencil or a palindrome matches only
+    /// once. Different stencils are all searched for, even when one is a
+    /// turn of another.
+    fn oriented(&self) -> Vec<Oriented> {
+        let mirrors: &[bool] = if self.mirrors { &[false, true] } else { &[false] };
+        let mut oriented = Vec::new();
+        for (stencil, cells) in self.stencils.iter().enumerate() {
+            let mut seen = Vec::new();
+            for eighth_turns in self.turns.eighth_turns() {
+                for mirrored in mirrors {
+                    let orientation = Orientation { eighth_turns, mirrored: *mirrored };
+                    let cells = cells.oriented(orientation);
+                    let corner = Point::new(cells.iter().map(|(point, _)| point.x).min().unwrap(),
+                                            cells.iter().map(|(point, _)| point.y).min().unwrap());
+                    let mut shape: Vec<(Point, char)> = cells.iter().map(|(point, letter)| (point.minus(&corner), *letter)).collect();
+                    shape.sort();
+                    if !seen.contains(&shape) {
+                        seen.push(shape);
+                        oriented.push(Oriented { stencil, orientation, cells });
+                    }
+                }
+            }
+        }
+        oriented
+    }
+
+    /// Every match in `map`, row by row.
+    pub fn run(&self, map: &Grid<char>) -> Vec<Match> {
+        let oriented = self.oriented();
+        map.iter_points()
+           .flat_map(|at| {
+               oriented.iter()
+                       .filter(move |oriented| oriented.matches(map, &at))
+                       .map(move |oriented| Match {
+                           stencil: oriented.stencil,
+                           at,
+                           orientation: oriented.orientation,
+                           points: oriented.cells.iter().map(|(offset, _)| at.add(offset)).collect(),
+                       })
+           })
+           .collect()
+    }
+
+    /// How many matches there are in `map`.
+    pub fn count(&self, map: &Grid<char>) -> usize {
+        let oriented = self.oriented();
+        map.iter_points()
+           .map(|at| oriented.iter().filter(|oriented| oriented.matches(map, &at)).count())
+           .sum()
+    }
+}
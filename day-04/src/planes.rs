@@ -0,0 +1,254 @@
+//! Counting words and crosses in letter grids too large to hold in memory.
+//!
+//! Every row is packed into bit planes, one bit per column for each letter
+//! that is looked for. A word starts at the columns where the plane of its
+//! first letter, the plane of its second letter shifted by one step, and so
+//! on all have a bit set, which checks 64 columns with a handful of ANDs.
+//!
+//! The rows are read in bands. A band counts the matches that start in its
+//! rows, looking into the first rows of the next band for the ends of
+//! vertical and diagonal ones. Bands are counted in parallel, a batch of
+//! them at a time, so only the rows of one batch are held at once.
+
+use rayon::prelude::*;
+use common::{stream_lines, InputError, Line, ParseError};
+
+/// What to count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// A word in any of the eight directions, as part 1 counts "XMAS".
+    Word(Vec<char>),
+    /// Two copies of a word crossing at its middle letter, each along a
+    /// diagonal and either way round, as part 2 counts "MAS".
+    Cross(Vec<char>),
+}
+
+impl Target {
+    /// A word to count, `None` when it has no letters.
+    pub fn word(word: &str) -> Option<Self> {
+        let letters: Vec<char> = word.chars().collect();
+        if letters.is_empty() { None } else { Some(Target::Word(letters)) }
+    }
+
+    /// A cross of `word` to count, `None` when it has no middle letter.
+    pub fn cross(word: &str) -> Option<Self> {
+        let letters: Vec<char> = word.chars().collect();
+        if letters.len() % 2 == 1 { Some(Target::Cross(letters)) } else { None }
+    }
+
+    fn letters(&self) -> &[char] {
+        match self {
+            Target::Word(letters) | Target::Cross(letters) => letters,
+        }
+    }
+
+    /// How many rows a match spans at most.
+    fn rows(&self) -> usize {
+        self.letters().len()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// How many rows a band has.
+    pub band: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { band: 1024 }
+    }
+}
+
+/// Counts the targets in rows handed to it one at a time.
+pub struct Counter {
+    targets: Vec<Target>,
+    /// The targets with their letters as indices of planes.
+    planned: Vec<Planned>,
+    /// The letters with a plane, in the order of the planes in a row.
+    letters: Vec<char>,
+    band: usize,
+    /// How many rows the bands of a batch hold, without the rows they look
+    /// into after them.
+    batch: usize,
+    /// How many rows after its own a band looks into.
+    overlap: usize,
+    width: Option<usize>,
+    /// The planes of the rows not counted yet, each row holding the planes of
+    /// all letters one after the other.
+    rows: Vec<Vec<u64>>,
+    counts: Vec<usize>,
+}
+
+/// A target with its letters replaced by the index of their plane.
+enum Planned {
+    Word(Vec<usize>),
+    Cross(Vec<usize>),
+}
+
+impl Counter {
+    pub fn new(targets: &[Target], options: &Options) -> Self {
+        let mut letters: Vec<char> = targets.iter().flat_map(|target| target.letters().iter().copied()).collect();
+        letters.sort_unstable();
+        letters.dedup();
+        let plane = |letter: &char| letters.binary_search(letter).unwrap();
+        let planned = targets.iter()
+                             .map(|target| match target {
+                                 Target::Word(word) => Planned::Word(word.iter().map(plane).collect()),
+                                 Target::Cross(word) => Planned::Cross(word.iter().map(plane).collect()),
+                             })
+                             .collect();
+
+        let band = options.band.max(1);
+        Counter { targets: targets.to_vec(),
+                  planned,
+                  letters,
+                  band,
+                  batch: band * rayon::current_num_threads(),
+                  overlap: targets.iter().map(Target::rows).max().unwrap_or(1) - 1,
+                  width: None,
+                  rows: Vec::new(),
+                  counts: vec![0; targets.len()] }
+    }
+
+    /// Adds the next row of the grid.
+    pub fn push(&mut self, line: &Line) -> Result<(), ParseError> {
+        let row = line.parse_chars("a letter", Some)?;
+        let width = *self.width.get_or_insert(row.len());
+        if row.len() != width {
+            return Err(line.error(row.len().min(width) + 1, format!("a row of {} cells", width)));
+        }
+
+        let words = width.div_ceil(64);
+        let mut planes = vec![0; self.letters.len() * words];
+        for (column, letter) in row.iter().enumerate() {
+            if let Ok(plane) = self.letters.binary_search(letter) {
+                planes[plane * words + column / 64] |= 1 << (column % 64);
+            }
+        }
+        self.rows.push(planes);
+
+        if self.rows.len() >= self.batch + self.overlap {
+            self.count(self.batch);
+        }
+        Ok(())
+    }
+
+    /// How often every target occurs in the rows pushed, in the order of the
+    /// targets.
+    pub fn finish(mut self) -> Vec<usize> {
+        self.count(self.rows.len());
+        self.counts
+    }
+
+    /// Counts the matches starting in the first `own` rows held, then drops
+    /// those rows.
+    fn count(&mut self, own: usize) {
+        let starts: Vec<usize> = (0..own).step_by(self.band).collect();
+        let counts: Vec<Vec<usize>> = starts.par_iter()
+                                            .map(|start| self.count_band(*start..(*start + self.band).min(own)))
+                                            .collect();
+        for band in counts {
+            for (total, count) in self.counts.iter_mut().zip(band) {
+                *total += count;
+            }
+        }
+        self.rows.drain(..own);
+    }
+
+    fn count_band(&self, tops: std::ops::Range<usize>) -> Vec<usize> {
+        let mut counts = vec![0; self.targets.len()];
+        for top in tops {
+            for (count, target) in counts.iter_mut().zip(&self.planned) {
+                *count += match target {
+                    Planned::Word(word) => self.count_words(top, word),
+                    Planned::Cross(word) => self.count_crosses(top, word),
+                };
+            }
+        }
+        counts
+    }
+
+    fn words(&self) -> usize {
+        self.width.unwrap_or(0).div_ceil(64)
+    }
+
+    /// Word `word` of the plane of letter `plane` in row `row`, shifted so that
+    /// its bit for a column holds the bit for `shift` columns to the right.
+    fn shifted(&self, row: usize, plane: usize, word: usize, shift: i64) -> u64 {
+        let words = self.words();
+        let bits = &self.rows[row][plane * words..(plane + 1) * words];
+        let get = |index: i64| if index < 0 { 0 } else { bits.get(index as usize).copied().unwrap_or(0) };
+
+        let start = word as i64 * 64 + shift;
+        let (index, offset) = (start.div_euclid(64), start.rem_euclid(64) as u32);
+        if offset == 0 {
+            get(index)
+        } else {
+            (get(index) >> offset) | (get(index + 1) << (64 - offset))
+        }
+    }
+
+    /// The columns `first` columns to the left of which `letters` start in
+    /// row `top`, going `dx` columns and `dy` rows per letter, for one word
+    /// of the planes.
+    fn line(&self, top: usize, word: usize, letters: &[usize], first: i64, dx: i64, dy: usize) -> u64 {
+        let mut columns = !0;
+        for (i, plane) in letters.iter().enumerate() {
+            columns &= self.shifted(top + i * dy, *plane, word, first + i as i64 * dx);
+            if columns == 0 {
+                break;
+            }
+        }
+        columns
+    }
+
+    /// How many times `word` starts or ends in row `top`, counting the
+    /// matches that go down from there.
+    fn count_words(&self, top: usize, word: &[usize]) -> usize {
+        let reversed: Vec<usize> = word.iter().rev().copied().collect();
+        // A palindrome reads the same both ways, and a single letter the same
+        // in every direction
+        let spellings: &[&[usize]] = if reversed == word { &[word] } else { &[word, &reversed] };
+        let directions: &[(i64, usize)] = if word.len() == 1 { &[(1, 0)] } else { &[(1, 0), (0, 1), (1, 1), (-1, 1)] };
+
+        let mut count = 0;
+        for (dx, dy) in directions {
+            if top + (word.len() - 1) * dy >= self.rows.len() {
+                continue;
+            }
+            for letters in spellings {
+                count += (0..self.words()).map(|i| self.line(top, i, letters, 0, *dx, *dy).count_ones() as usize)
+                                          .sum::<usize>();
+            }
+        }
+        count
+    }
+
+    /// How many crosses of `word` have their top row in row `top`.
+    fn count_crosses(&self, top: usize, word: &[usize]) -> usize {
+        if top + word.len() > self.rows.len() {
+            return 0;
+        }
+        let reversed: Vec<usize> = word.iter().rev().copied().collect();
+        // The diagonal going down to the left starts at the right end of the
+        // cross, which is this far from its left end
+        let across = word.len() as i64 - 1;
+        (0..self.words()).map(|i| {
+                             let down_right = self.line(top, i, word, 0, 1, 1) | self.line(top, i, &reversed, 0, 1, 1);
+                             let down_left = self.line(top, i, word, across, -1, 1) | self.line(top, i, &reversed, across, -1, 1);
+                             (down_right & down_left).count_ones() as usize
+                         })
+                         .sum()
+    }
+}
+
+/// How often every target occurs in the grid in `filename`, reading it a
+/// batch of bands at a time.
+pub fn count_file(filename: &str, targets: &[Target], options: &Options) -> Result<Vec<usize>, InputError> {
+    let mut counter = Counter::new(targets, options);
+    for line in stream_lines(filename)? {
+        counter.push(&line?)?;
+    }
+    Ok(counter.finish())
+}
@@ -0,0 +1 @@
+common::example_tests!(day_04::Day04; test, test_small);
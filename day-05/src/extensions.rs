@@ -0,0 +1,311 @@
+//! Counting and listing the orders of an update that satisfy the rules, its
+//! linear extensions.
+//!
+//! The pages of an update fall apart into groups, the pages in a group
+//! related to one another by rules and to no page outside it. The orders of
+//! the update are the orders of every group, interleaved in every way, so
+//! they are counted group by group and put together with binomials. A page
+//! no rule mentions is a group of its own.
+//!
+//! The orders of a group are counted over the sets of its pages that can be
+//! printed first: a set whose pages have all their predecessors in the set
+//! too. For every such set, the number of ways to print it first, and the
+//! number of ways to print the rest after it. A group the rules put in a
+//! total order only has one such set of every size, a group of pages with
+//! few rules between them has a great many, so groups with more than
+//! [`MAX_STARTS`] of them are not counted.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use crate::ordering::Precedence;
+use crate::validation::Unorderable;
+
+/// How many sets of pages that can be printed first a group may have for
+/// its orders to be counted.
+pub const MAX_STARTS: usize = 1 << 18;
+
+/// Some pages of a group, as bits of their positions in it.
+type Pages = Vec<u64>;
+
+/// How the orders of an update that satisfy the rules look.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Orderings {
+    /// How many orders satisfy the rules, saturating at `u128::MAX`.
+    pub count: u128,
+    /// Every page that is in the middle of some of those orders, with the
+    /// number of orders it is in the middle of, by page.
+    pub middles: Vec<(usize, u128)>,
+}
+
+impl Orderings {
+    /// Whether every order that satisfies the rules has the same page in the
+    /// middle, which makes part 2's answer for the update unambiguous.
+    pub fn middle_is_fixed(&self) -> bool {
+        self.middles.len() == 1
+    }
+}
+
+/// Why the orders of an update were not counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Uncountable {
+    /// No order satisfies the rules.
+    Unorderable(Unorderable),
+    /// A group of pages of the update has more than [`MAX_STARTS`] sets that
+    /// can be printed first.
+    TooLarge { update: Vec<usize>, group: usize },
+}
+
+impl fmt::Display for Uncountable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Uncountable::Unorderable(unorderable) => write!(f, "{}", unorderable),
+            Uncountable::TooLarge { update, group } => {
+                let pages: Vec<String> = update.iter().map(usize::to_string).collect();
+                write!(f,
+                       "the orders of the pages {} can not be counted, {} of them have too few rules between them",
+                       pages.join(","),
+                       group)
+            }
+        }
+    }
+}
+
+impl Error for Uncountable {}
+
+/// The orders of one group of pages of an update.
+struct Group {
+    /// The positions of its pages in the update.
+    members: Vec<usize>,
+    count: u128,
+    /// By member and position in the group, how many orders of the group
+    /// put the member there, for the positions that can end up in the
+    /// middle of the update.
+    positions: HashMap<(usize, usize), u128>,
+}
+
+/// The groups of the pages of `update`, as positions in the update, in the
+/// order of their first page.
+fn group_pages(precedence: &Precedence, update: &[usize]) -> Vec<Vec<usize>> {
+    let mut group: Vec<usize> = (0..update.len()).collect();
+    fn root(group: &mut [usize], mut page: usize) -> usize {
+        while group[page] != page {
+            group[page] = group[group[page]];
+            page = group[page];
+        }
+        page
+    }
+    for first in 0..update.len() {
+        for second in first + 1..update.len() {
+            if precedence.precedes(update[first], update[second]) || precedence.precedes(update[second], update[first]) {
+                let (first, second) = (root(&mut group, first), root(&mut group, second));
+                group[first.max(second)] = first.min(second);
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut index: HashMap<usize, usize> = HashMap::new();
+    for page in 0..update.len() {
+        let root = root(&mut group, page);
+        let next = groups.len();
+        let at = *index.entry(root).or_insert(next);
+        if at == groups.len() {
+            groups.push(Vec::new());
+        }
+        groups[at].push(page);
+    }
+    groups
+}
+
+fn contains(pages: &Pages, member: usize) -> bool {
+    pages[member / 64] & (1 << (member % 64)) != 0
+}
+
+fn with(pages: &Pages, member: usize) -> Pages {
+    let mut pages = pages.clone();
+    pages[member / 64] |= 1 << (member % 64);
+    pages
+}
+
+/// The members that can be printed next after `printed`.
+fn next_pages<'a>(predecessors: &'a [Pages], printed: &'a Pages) -> impl Iterator<Item = usize> + 'a {
+    (0..predecessors.len()).filter(move |member| {
+        !contains(printed, *member) && predecessors[*member].iter().zip(printed).all(|(before, printed)| before & !printed == 0)
+    })
+}
+
+/// Counts the orders of the group of `members`, `None` when it has too many
+/// sets that can be printed first. Only the positions in `window` are kept
+/// track of.
+fn count_group(precedence: &Precedence, update: &[usize], members: Vec<usize>, window: std::ops::Range<usize>) -> Option<Group> {
+    let words = members.len().div_ceil(64);
+    let predecessors: Vec<Pages> = members.iter()
+                                          .map(|page| {
+                                              let mut before = vec![0; words];
+                                              for (member, other) in members.iter().enumerate() {
+                                                  if precedence.precedes(update[*other], update[*page]) {
+                                                      before[member / 64] |= 1 << (member % 64);
+                                                  }
+                                              }
+                                              before
+                                          })
+                                          .collect();
+
+    // For the sets of every size that can be printed first, how many ways
+    // there are to print them
+    let mut layers: Vec<HashMap<Pages, u128>> = vec![HashMap::from([(vec![0; words], 1)])];
+    let mut starts = 1;
+    for _ in 0..members.len() {
+        let mut next: HashMap<Pages, u128> = HashMap::new();
+        for (printed, ways) in layers.last().unwrap() {
+            for member in next_pages(&predecessors, printed) {
+                let count = next.entry(with(printed, member)).or_default();
+                *count = count.saturating_add(*ways);
+            }
+        }
+        starts += next.len();
+        if starts > MAX_STARTS {
+            return None;
+        }
+        layers.push(next);
+    }
+    let all = (0..members.len()).fold(vec![0; words], |pages, member| with(&pages, member));
+    let count = layers[members.len()].get(&all).copied().unwrap_or(0);
+
+    // Going back, how many ways there are to print the rest after them
+    let mut rest: HashMap<Pages, u128> = HashMap::from([(all, 1)]);
+    let mut positions: HashMap<(usize, usize), u128> = HashMap::new();
+    for size in (0..members.len()).rev() {
+        for (printed, ways) in &layers[size] {
+            let mut after = 0u128;
+            for member in next_pages(&predecessors, printed) {
+                let Some(rest_after) = rest.get(&with(printed, member)) else {
+                    continue;
+                };
+                after = after.saturating_add(*rest_after);
+                if window.contains(&size) {
+                    let orders = positions.entry((member, size)).or_default();
+                    *orders = orders.saturating_add(ways.saturating_mul(*rest_after));
+                }
+            }
+            if after > 0 {
+                rest.insert(printed.clone(), after);
+            }
+        }
+    }
+    Some(Group { members, count, positions })
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// `n` over `k`, saturating at `u128::MAX`.
+fn binomial(n: usize, k: usize) -> u128 {
+    if k > n {
+        return 0;
+    }
+    let mut binomial: u128 = 1;
+    for i in 0..k.min(n - k) as u128 {
+        // Times n - i, over i + 1, without an intermediate that overflows
+        // when the result does not
+        let divisor = gcd(binomial, i + 1);
+        match (binomial / divisor).checked_mul((n as u128 - i) / ((i + 1) / divisor)) {
+            Some(next) => binomial = next,
+            None => return u128::MAX,
+        }
+    }
+    binomial
+}
+
+/// The ways to print the groups of `sizes` with `counts` orders each,
+/// interleaved in every way, with how many pages that is.
+fn interleavings(sizes: &[usize], counts: &[u128]) -> (u128, usize) {
+    sizes.iter().zip(counts).fold((1, 0), |(ways, pages), (size, count)| {
+        (ways.saturating_mul(binomial(pages + size, *size)).saturating_mul(*count), pages + size)
+    })
+}
+
+/// Counts the orders of `update` that satisfy the rules.
+pub fn count(precedence: &Precedence, update: &[usize]) -> Result<Orderings, Uncountable> {
+    precedence.order(update).map_err(Uncountable::Unorderable)?;
+
+    let total = update.len();
+    let middle = total / 2;
+    let mut groups: Vec<Group> = Vec::new();
+    for members in group_pages(precedence, update) {
+        // The pages of the other groups fill all but the group's own places,
+        // so only some of its positions can end up in the middle
+        let others = total - members.len();
+        let window = middle.saturating_sub(others)..middle + 1;
+        let size = members.len();
+        let group = count_group(precedence, update, members, window)
+            .ok_or_else(|| Uncountable::TooLarge { update: update.to_vec(), group: size })?;
+        groups.push(group);
+    }
+
+    let sizes: Vec<usize> = groups.iter().map(|group| group.members.len()).collect();
+    let counts: Vec<u128> = groups.iter().map(|group| group.count).collect();
+    let (count, _) = interleavings(&sizes, &counts);
+
+    let mut middles: HashMap<usize, u128> = HashMap::new();
+    for (i, group) in groups.iter().enumerate() {
+        // The orders of all other groups interleaved, from the groups before
+        // and after this one
+        let (before, pages_before) = interleavings(&sizes[..i], &counts[..i]);
+        let (after, pages_after) = interleavings(&sizes[i + 1..], &counts[i + 1..]);
+        let others = before.saturating_mul(after).saturating_mul(binomial(pages_before + pages_after, pages_after));
+
+        let size = group.members.len();
+        for ((member, position), orders) in &group.positions {
+            // The member is in the middle when `position` pages of the group
+            // come before the middle and the rest after it
+            let places = binomial(middle, *position).saturating_mul(binomial(total - 1 - middle, size - 1 - position));
+            let ways = orders.saturating_mul(places).saturating_mul(others);
+            if ways > 0 {
+                let count = middles.entry(update[group.members[*member]]).or_default();
+                *count = count.saturating_add(ways);
+            }
+        }
+    }
+
+    let mut middles: Vec<(usize, u128)> = middles.into_iter().collect();
+    middles.sort_unstable();
+    Ok(Orderings { count, middles })
+}
+
+/// Up to `limit` of the orders of `update` that satisfy the rules, sorted by
+/// the positions their pages had in the update. Fails when the rules
+/// contradict one another.
+pub fn list(precedence: &Precedence, update: &[usize], limit: usize) -> Result<Vec<Vec<usize>>, Unorderable> {
+    fn extend(predecessors: &[Vec<usize>], update: &[usize], printed: &mut [bool], order: &mut Vec<usize>, orders: &mut Vec<Vec<usize>>, limit: usize) {
+        if order.len() == update.len() {
+            orders.push(order.clone());
+            return;
+        }
+        for page in 0..update.len() {
+            if orders.len() == limit {
+                return;
+            }
+            if printed[page] || predecessors[page].iter().any(|before| !printed[*before]) {
+                continue;
+            }
+            printed[page] = true;
+            order.push(update[page]);
+            extend(predecessors, update, printed, order, orders, limit);
+            order.pop();
+            printed[page] = false;
+        }
+    }
+
+    precedence.order(update)?;
+    let predecessors: Vec<Vec<usize>> = update.iter()
+                                              .map(|page| (0..update.len()).filter(|before| precedence.precedes(update[*before], *page)).collect())
+                                              .collect();
+    let mut orders = Vec::new();
+    if limit > 0 {
+        extend(&predecessors, update, &mut vec![false; update.len()], &mut Vec::with_capacity(update.len()), &mut orders, limit);
+    }
+    Ok(orders)
+}
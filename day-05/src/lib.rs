@@ -0,0 +1,367 @@
+pub mod extensions;
+pub mod ordering;
+pub mod validation;
+
+use std::collections::{HashMap, HashSet};
+use common::{Answer, Line, Lines, ParseError, Solution};
+use extensions::{Orderings, Uncountable};
+use ordering::Precedence;
+use validation::{shortest_cycle, Cycle, Unorderable};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-05"
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(problem.solve1().into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        match problem.solve2() {
+            Ok(answer) => Some(answer.into()),
+            Err(error) => {
+                log::warn!("{}", error);
+                None
+            }
+        }
+    }
+}
+
+pub struct Problem {
+    rules: HashMap<usize, Vec<usize>>,
+    precedence: Precedence,
+    orders: Vec<Vec<usize>>,
+}
+
+impl Problem {
+    fn new(rules: HashMap<usize, Vec<usize>>, orders: Vec<Vec<usize>>) -> Self {
+        let precedence = Precedence::new(&rules);
+
+        Problem {
+            rules,
+            precedence,
+            orders
+        }
+    }
+
+    fn solve1(&self) -> usize {
+        self.orders.iter()
+                                    .filter(|order| !self.is_order_incorrect(order))
+                                    .map(|order| order.get(order.len() / 2).unwrap())
+                                    .sum()
+    }
+
+    fn solve2(&self) -> Result<usize, Unorderable> {
+        self.orders.iter()
+                                    .filter(|order| self.is_order_incorrect(order))
+                                    .map(|order| self.put_in_order(order))
+                                    .map(|o| o.map(|o| o[o.len() / 2])) // Directly access the middle value
+                                    .sum()
+    }
+
+    /// The updates, as they are in the input.
+    pub fn updates(&self) -> &[Vec<usize>] {
+        &self.orders
+    }
+
+    /// The fewest rules that contradict one another, `None` when all rules
+    /// can hold at once. Contradicting rules only matter to the updates that
+    /// have all their pages, see [`Problem::update_cycle`].
+    pub fn cycle(&self) -> Option<Cycle> {
+        shortest_cycle(&self.rules, None)
+    }
+
+    /// The fewest rules between the pages of `update` that contradict one
+    /// another, `None` when the update can be put in order.
+    pub fn update_cycle(&self, update: &[usize]) -> Option<Cycle> {
+        let pages: HashSet<usize> = update.iter().copied().collect();
+        shortest_cycle(&self.rules, Some(&pages))
+    }
+
+    /// The pages of `order` in an order that satisfies the rules.
+    pub fn put_in_order(&self, order: &[usize]) -> Result<Vec<usize>, Unorderable> {
+        self.precedence.order(order)
+    }
+
+    fn is_order_incorrect(&self, order: &[usize]) -> bool {
+        !self.precedence.is_ordered(order)
+    }
+
+    /// How many orders of `update` satisfy the rules, and which pages they
+    /// put in the middle.
+    pub fn orderings(&self, update: &[usize]) -> Result<Orderings, Uncountable> {
+        extensions::count(&self.precedence, update)
+    }
+
+    /// Up to `limit` of the orders of `update` that satisfy the rules.
+    pub fn list_orderings(&self, update: &[usize], limit: usize) -> Result<Vec<Vec<usize>>, Unorderable> {
+        extensions::list(&self.precedence, update, limit)
+    }
+
+    /// The rules, ready to check and correct updates with.
+    pub fn precedence(&self) -> &Precedence {
+        &self.precedence
+    }
+
+}
+
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let mut parsing_rules = true;
+
+    let mut rules: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut orders: Vec<Vec<usize>> = Vec::new();
+
+    for line in lines {
+        if line.text.is_empty() {
+            parsing_rules = false;
+            continue;
+        }
+        if parsing_rules {
+            let (key, value) = read_rule(&line)?;
+
+            // Insert or append the value to the Vec for the key
+            rules.entry(key).or_default().push(value);
+        } else {
+            orders.push(read_update(&line)?);
+        }
+    }
+
+    Ok(Problem::new(rules, orders))
+}
+
+/// A rule, as the page before and the page after.
+fn read_rule(line: &Line) -> Result<(usize, usize), ParseError> {
+    let (key, value) = line.split_once("|")?;
+    let key = line.parse::<usize>(key, "a page number")?;
+    let value = line.parse::<usize>(value, "a page number")?;
+    Ok((key, value))
+}
+
+fn read_update(line: &Line) -> Result<Vec<usize>, ParseError> {
+    line.text.split(',')
+        .map(|x| line.parse::<usize>(x, "a page number"))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::validation::Rule;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn problem(text: &str) -> Problem {
+        read_input(Lines::from_text("<string>", text)).unwrap()
+    }
+
+    fn rules(pairs: &[(usize, usize)]) -> Vec<Rule> {
+        pairs.iter().map(|(before, after)| Rule { before: *before, after: *after }).collect()
+    }
+
+    #[test]
+    fn the_shortest_cycle_is_reported() {
+        let problem = problem("1|2\n2|3\n3|4\n4|1\n3|1\n5|6\n\n1,2,4\n1,2,3,4\n");
+        assert_eq!(problem.cycle().unwrap().rules, rules(&[(1, 2), (2, 3), (3, 1)]));
+        assert_eq!(problem.update_cycle(&[1, 2, 4]), None);
+        assert_eq!(problem.update_cycle(&[4, 1, 2, 3]).unwrap().rules, rules(&[(1, 2), (2, 3), (3, 1)]));
+        assert_eq!(problem.update_cycle(&[4, 1, 3]), None);
+    }
+
+    #[test]
+    fn updates_that_can_not_be_ordered_are_an_error() {
+        let problem = problem("1|2\n2|3\n3|1\n3|4\n\n3,4\n4,3\n2,1,4\n3,2,1,4\n");
+        assert_eq!(problem.put_in_order(&[4, 3]), Ok(vec![3, 4]));
+        let error = problem.put_in_order(&[3, 2, 1, 4]).unwrap_err();
+        assert_eq!(error.cycle.rules, rules(&[(1, 2), (2, 3), (3, 1)]));
+        assert_eq!(error.to_string(), "the pages 3,2,1,4 can not be put in order, the rules 1|2, 2|3, 3|1 contradict one another");
+        assert_eq!(problem.solve2(), Err(error));
+    }
+
+    /// Puts `update` in order the way it used to be done, taking the first
+    /// page none of whose predecessors are left, until none is.
+    fn put_in_order_slowly(rules: &HashMap<usize, Vec<usize>>, update: &[usize]) -> Vec<usize> {
+        let mut todo_list = update.to_vec();
+        let mut ordered_list = Vec::new();
+        while let Some(&next) = todo_list.iter().find(|&&item| {
+            todo_list.iter().all(|before| rules.get(before).is_none_or(|afters| !afters.contains(&item)))
+        }) {
+            todo_list.retain(|&x| x != next);
+            ordered_list.push(next);
+        }
+        ordered_list
+    }
+
+    #[test]
+    fn the_precedence_bitset_orders_like_before() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for round in 0..200 {
+            // Every other round all pairs of pages have a rule, as in the puzzle input
+            let pages = rng.random_range(3..43);
+            let rank: Vec<u64> = (0..pages).map(|_| rng.random_range(0..1000)).collect();
+            let mut text = String::new();
+            for first in 0..pages {
+                for second in first + 1..pages {
+                    if round % 2 == 0 || rng.random_range(0..3) == 0 {
+                        let (before, after) = if rank[first] < rank[second] || rng.random_range(0..60) == 0 { (first, second) } else { (second, first) };
+                        text += &format!("{}|{}\n", before, after);
+                    }
+                }
+            }
+            text += "\n";
+            for _ in 0..20 {
+                let mut update: Vec<(u64, usize)> = (0..pages).map(|page| (rng.random_range(0..4000), page)).filter(|(key, _)| key % 4 == 0).collect();
+                // A page no rule mentions
+                if rng.random_range(0..4) == 0 {
+                    update.push((rng.random_range(0..1000), pages));
+                }
+                if update.is_empty() {
+                    continue;
+                }
+                update.sort_unstable();
+                let update: Vec<usize> = update.into_iter().map(|(_, page)| page).collect();
+                let update: Vec<String> = update.iter().map(usize::to_string).collect();
+                text += &format!("{}\n", update.join(","));
+            }
+            let problem = problem(&text);
+
+            let mut expected = ordering::Middles::default();
+            for (i, update) in problem.updates().iter().enumerate() {
+                let slowly = put_in_order_slowly(&problem.rules, update);
+                let ordered = problem.put_in_order(update);
+                if slowly.len() < update.len() {
+                    assert!(ordered.is_err());
+                    expected.unorderable += 1;
+                    expected.first_unorderable = expected.first_unorderable.or(ordered.err().map(|error| (i, error)));
+                } else if slowly == *update {
+                    assert_eq!(ordered.as_ref(), Ok(update));
+                    expected.ordered += update[update.len() / 2];
+                } else {
+                    assert_eq!(ordered.as_ref(), Ok(&slowly));
+                    expected.corrected += slowly[slowly.len() / 2];
+                }
+            }
+            assert_eq!(problem.precedence().middles(problem.updates()), expected);
+        }
+    }
+
+    #[test]
+    fn batches_add_up_to_the_answers_of_the_whole_input() {
+        let text = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n\
+                    75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n\
+                    75,47,61,53,29\n97,61,53,29,13\n\n75,29,13\n75,97,47,61,53\n\n\n61,13,29\n97,13,75,29,47\n\n";
+        let whole = problem(text);
+        let dir = tempfile::tempdir().unwrap();
+        let filename = dir.path().join("input.dat");
+        std::fs::write(&filename, text).unwrap();
+        for batch in [1, 2, 4, 100] {
+            let middles = ordering::middles_file(&filename.to_string_lossy(), batch).unwrap();
+            assert_eq!((middles.ordered, middles.corrected), (whole.solve1(), whole.solve2().unwrap()), "batch {}", batch);
+            assert_eq!((middles.ordered, middles.corrected), (143, 123));
+        }
+    }
+
+    #[test]
+    fn orderings_are_counted_with_their_middle_pages() {
+        let queue = problem("1|2\n1|3\n2|4\n3|4\n4|5\n\n5,4,3,2,1\n");
+        let orderings = queue.orderings(&[5, 4, 3, 2, 1]).unwrap();
+        assert_eq!(orderings.count, 2);
+        assert_eq!(orderings.middles, [(2, 1), (3, 1)]);
+        assert!(!orderings.middle_is_fixed());
+        assert_eq!(queue.list_orderings(&[5, 4, 3, 2, 1], 10), Ok(vec![vec![1, 3, 2, 4, 5], vec![1, 2, 3, 4, 5]]));
+        assert_eq!(queue.list_orderings(&[5, 4, 3, 2, 1], 1).unwrap().len(), 1);
+
+        let orderings = queue.orderings(&[4, 2, 1]).unwrap();
+        assert!(orderings.middle_is_fixed());
+        assert_eq!(orderings, Orderings { count: 1, middles: vec![(2, 1)] });
+        assert_eq!(queue.orderings(&[6, 7, 8]).unwrap(), Orderings { count: 6, middles: vec![(6, 2), (7, 2), (8, 2)] });
+
+        let cyclic = problem("1|2\n2|1\n\n1,2\n");
+        assert!(matches!(cyclic.orderings(&[1, 2]), Err(Uncountable::Unorderable(error)) if error.cycle.rules.len() == 2));
+        assert!(cyclic.list_orderings(&[2, 1], 5).is_err());
+    }
+
+    #[test]
+    fn orderings_of_large_updates_are_counted_or_refused() {
+        let unrelated: Vec<usize> = (1..=30).collect();
+        let orderings = problem("1|2\n\n1,2\n").orderings(&(3..=32).collect::<Vec<usize>>()).unwrap();
+        let factorial = |n: u128| (1..=n).product::<u128>();
+        assert_eq!(orderings.count, factorial(30));
+        assert_eq!(orderings.middles, unrelated.iter().map(|page| (page + 2, factorial(29))).collect::<Vec<_>>());
+
+        let chain: Vec<String> = (1..130).map(|page| format!("{}|{}", page, page + 1)).collect();
+        let update: Vec<usize> = (1..=130).rev().collect();
+        let orderings = problem(&format!("{}\n\n1,2\n", chain.join("\n"))).orderings(&update).unwrap();
+        assert_eq!(orderings, Orderings { count: 1, middles: vec![(66, 1)] });
+
+        let star: Vec<String> = (1..=20).map(|page| format!("0|{}", page)).collect();
+        let queue = problem(&format!("{}\n\n1,2\n", star.join("\n")));
+        let update: Vec<usize> = (0..=20).collect();
+        assert_eq!(queue.orderings(&update), Err(Uncountable::TooLarge { update: update.clone(), group: 21 }));
+        assert_eq!(queue.list_orderings(&update, 2).unwrap().len(), 2);
+    }
+
+    /// All orders of `pages` that break none of the rules.
+    fn all_orderings(problem: &Problem, pages: &[usize]) -> Vec<Vec<usize>> {
+        if pages.is_empty() {
+            return vec![Vec::new()];
+        }
+        (0..pages.len()).filter(|i| pages.iter().all(|before| !problem.precedence().precedes(*before, pages[*i])))
+                        .flat_map(|i| {
+                            let rest: Vec<usize> = pages.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, page)| *page).collect();
+                            all_orderings(problem, &rest).into_iter().map(move |mut order| {
+                                order.insert(0, pages[i]);
+                                order
+                            })
+                        })
+                        .collect()
+    }
+
+    #[test]
+    fn orderings_agree_with_trying_every_permutation() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..100 {
+            let pages = rng.random_range(1..=7);
+            let mut text = String::new();
+            for before in 0..pages {
+                for after in before + 1..pages {
+                    if rng.random_range(0..3) == 0 {
+                        text += &format!("{}|{}\n", before, after);
+                    }
+                }
+            }
+            let update: Vec<usize> = (0..pages).rev().collect();
+            let queue = problem(&format!("{}\n{}\n", text, update.iter().map(usize::to_string).collect::<Vec<_>>().join(",")));
+
+            let mut expected = all_orderings(&queue, &update);
+            let orderings = queue.orderings(&update).unwrap();
+            assert_eq!(orderings.count, expected.len() as u128);
+            let mut middles: HashMap<usize, u128> = HashMap::new();
+            for order in &expected {
+                *middles.entry(order[order.len() / 2]).or_default() += 1;
+            }
+            let mut middles: Vec<(usize, u128)> = middles.into_iter().collect();
+            middles.sort_unstable();
+            assert_eq!(orderings.middles, middles);
+
+            let mut listed = queue.list_orderings(&update, usize::MAX).unwrap();
+            listed.sort();
+            expected.sort();
+            assert_eq!(listed, expected);
+        }
+    }
+}
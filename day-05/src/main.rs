@@ -1,125 +1,172 @@
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{self, BufReader, BufRead};
 use std::env;
-
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    let input = &args[1];
-
-    let problem = read_input(input)?;
-    problem.solve1();
-    problem.solve2();
-    Ok(())
+use std::error::Error;
+use std::process::ExitCode;
+use clap::builder::RangedU64ValueParser;
+use clap::{Parser, Subcommand};
+use common::{SolveArgs, Solution};
+use day_05::extensions::MAX_STARTS;
+use day_05::ordering::middles_file;
+use day_05::Day05;
+
+#[derive(Parser)]
+#[command(about = "Solves day 5, or looks closer at its rules and updates",
+          after_help = "An input named like a command is solved with `day-05 -- batch`",
+          args_conflicts_with_subcommands = true,
+          arg_required_else_help = true)]
+struct Cli {
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
+
+    #[command(subcommand)]
+    mode: Option<Mode>,
 }
 
-
-struct Problem {
-    rules: HashMap<usize, Vec<usize>>,
-    reverse_rules: HashMap<usize, Vec<usize>>,
-    orders: Vec<Vec<usize>>,
+#[derive(Subcommand)]
+enum Mode {
+    /// Looks for page ordering rules in the input that contradict one
+    /// another, among all rules and among the rules between the pages of
+    /// every update, and shows the fewest rules that do. Fails when an update
+    /// can not be put in order
+    Validate {
+        /// Rules and updates, `-` for stdin
+        input: String,
+    },
+    /// Checks and corrects the updates in the input the way both parts do,
+    /// reading them a batch at a time and spreading every batch over all
+    /// threads, for print queues of millions of updates
+    Batch {
+        /// How many updates to read at once
+        #[arg(long, default_value_t = 1 << 16, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        batch: usize,
+
+        /// Rules and updates, `-` for stdin
+        input: String,
+    },
+    /// Counts for every update in the input how many orders of its pages
+    /// satisfy the rules, and which pages those orders put in the middle. An
+    /// update whose middle page differs between orders has no single answer
+    /// for part 2. Updates with a group of pages that has too few rules
+    /// between them to go through are left uncounted
+    #[command(after_help = format!("A group is too large to go through with over {} ways to start printing it", MAX_STARTS))]
+    Orderings {
+        /// Also lists the orders of updates that have at most this many
+        #[arg(long, value_name = "ORDERS", default_value_t = 0)]
+        list: usize,
+
+        /// Rules and updates, `-` for stdin
+        input: String,
+    },
 }
 
-impl Problem {
-    fn new(rules: HashMap<usize, Vec<usize>>, orders: Vec<Vec<usize>>) -> Self {
-
-        let reverse_rules: HashMap<usize, Vec<usize>> =
-            rules.iter()
-                 .flat_map(|(&key, values)|
-                                values.iter().map(move |&value| (value, key)))
-                 .fold(HashMap::new(), |mut acc, (value, key)| {
-                        acc.entry(value).or_insert_with(Vec::new).push(key);
-                        acc });
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let cli: Cli = common::parse_args(&Day05, &args);
+    let Some(mode) = cli.mode else {
+        return common::solve(&Day05, &cli.solve.expect("clap asks for an input when there is no command"));
+    };
+    match mode {
+        Mode::Validate { input } => validate(&input),
+        Mode::Batch { batch: size, input } => batch(&input, size),
+        Mode::Orderings { list, input } => orderings(&input, list),
+    }
+}
 
-        Problem {
-            rules,
-            reverse_rules,
-            orders
+fn validate(input: &str) -> ExitCode {
+    match print_validation(input) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
         }
     }
+}
 
-    fn solve1(&self) {
-        let res: usize = self.orders.iter()
-                                    .filter(|order| !self.is_order_incorrect(order))
-                                    .map(|order| order.get(order.len() / 2).unwrap())
-                                    .sum();
-        println!("you get {} if you add up the middle page number from those correctly-ordered updates",
-                 res)
-    }
+/// Shows what contradicts, and whether every update can be put in order.
+fn print_validation(input: &str) -> Result<bool, Box<dyn Error>> {
+    let problem = Day05.read_input(input, &Day05.default_params())?;
 
-    fn solve2(&self) {
-        let res: usize = self.orders.iter()
-                                    .filter(|order| self.is_order_incorrect(order))
-                                    .map(|order| self.put_in_order(order))
-                                    .map(|o| o[o.len() / 2]) // Directly access the middle value
-                                    .sum();
-        println!("you get {} if you add up the middle page numbers after correctly ordering just those updates",
-                 res)
+    match problem.cycle() {
+        Some(cycle) => println!("the rules {} contradict one another", cycle),
+        None => println!("all rules can hold at once"),
     }
 
-    fn put_in_order(&self, order: &Vec<usize>) -> Vec<usize> {
-        let mut todo_list = order.clone();
-        let mut ordered_list: Vec<usize> = Vec::new();
-
-        while let Some(&next) = todo_list.iter().find(|&&item| {
-            self.reverse_rules.get(&item).map_or(true, |deps| {
-                deps.iter().all(|&dep| !todo_list.contains(&dep))
-            })
-        }) {
-            todo_list.retain(|&x| x != next);
-            ordered_list.push(next);
+    let mut unorderable = 0;
+    for (i, update) in problem.updates().iter().enumerate() {
+        if let Some(cycle) = problem.update_cycle(update) {
+            let pages: Vec<String> = update.iter().map(usize::to_string).collect();
+            println!("update {} ({}): the rules {} contradict one another", i + 1, pages.join(","), cycle);
+            unorderable += 1;
         }
-
-        ordered_list
     }
-
-    fn is_order_incorrect(&self, order: &Vec<usize>) -> bool {
-        let order_map: HashMap<usize, usize> = order.iter()
-            .enumerate()
-            .fold(HashMap::new(), |mut acc, (idx, &val)| {
-                acc.insert(val, idx);
-                acc
-            });
-
-        order.iter()
-             .enumerate()
-             .any(|(idx, &val)| {
-                self.rules.get(&val)
-                    .map_or(false, |rule_values|
-                        rule_values.iter()
-                            .any(|&rule_value|
-                                order_map.get(&rule_value).unwrap_or(&order.len()) < &idx))
-            })
+    if unorderable == 0 {
+        println!("all {} updates can be put in order", problem.updates().len());
+    } else {
+        println!("{} of {} updates can not be put in order", unorderable, problem.updates().len());
     }
-
+    Ok(unorderable == 0)
 }
 
-
-fn read_input(filename: &String) ->  io::Result<Problem> {
-    let mut parsing_rules = true;
-    let file_in = File::open(filename)?;
-
-    let mut rules: HashMap<usize, Vec<usize>> = HashMap::new();
-    let mut orders: Vec<Vec<usize>> = Vec::new();
-
-    for line in BufReader::new(file_in).lines().map(|x| x.unwrap()) {
-        if line.is_empty() {
-            parsing_rules = false;
-            continue;
+fn batch(input: &str, size: usize) -> ExitCode {
+    match middles_file(input, size) {
+        Ok(middles) => {
+            println!("Part 1: {}", middles.ordered);
+            println!("Part 2: {}", middles.corrected);
+            if let Some((i, error)) = middles.first_unorderable {
+                println!("{} updates can not be put in order, the first is update {}: {}", middles.unorderable, i + 1, error);
+                return ExitCode::FAILURE;
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
         }
-        if parsing_rules {
-            let words: Vec<&str> = line.split('|').collect();
-            let key = words[0].parse::<usize>().unwrap();
-            let value = words[1].parse::<usize>().unwrap();
+    }
+}
 
-            // Insert or append the value to the Vec for the key
-            rules.entry(key).or_insert_with(Vec::new).push(value);
-        } else {
-            let order: Vec<usize> =
-                line.split(',').map(|x| x.parse::<usize>().unwrap()).collect();
-            orders.push(order);
+fn orderings(input: &str, list: usize) -> ExitCode {
+    match print_orderings(input, list) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
         }
     }
+}
 
-    Ok(Problem::new(rules, orders))
+/// Shows how many orders every update has, and which middle pages they give.
+fn print_orderings(input: &str, list: usize) -> Result<(), Box<dyn Error>> {
+    let problem = Day05.read_input(input, &Day05.default_params())?;
+
+    let (mut fixed, mut uncounted) = (0, 0);
+    for (i, update) in problem.updates().iter().enumerate() {
+        let pages: Vec<String> = update.iter().map(usize::to_string).collect();
+        let orderings = match problem.orderings(update) {
+            Ok(orderings) => orderings,
+            Err(error) => {
+                println!("update {}: {}", i + 1, error);
+                uncounted += 1;
+                continue;
+            }
+        };
+        let middles: Vec<String> = orderings.middles.iter().map(|(page, orders)| format!("{} ({})", page, orders)).collect();
+        let orders = if orderings.count == 1 { "order" } else { "orders" };
+        println!("update {} ({}): {} {}, middle {}", i + 1, pages.join(","), orderings.count, orders, middles.join(", "));
+        if orderings.middle_is_fixed() {
+            fixed += 1;
+        }
+        if orderings.count <= list as u128 {
+            for order in problem.list_orderings(update, list)? {
+                let pages: Vec<String> = order.iter().map(usize::to_string).collect();
+                println!("  {}", pages.join(","));
+            }
+        }
+    }
+    print!("{} of {} updates have the same middle page in every order", fixed, problem.updates().len());
+    if uncounted > 0 {
+        print!(", {} could not be counted", uncounted);
+    }
+    println!();
+    Ok(())
 }
@@ -0,0 +1,234 @@
+//! Checking and correcting the order of updates against the rules, for
+//! print queues of millions of updates.
+//!
+//! The rules are turned into a precedence bitset once: every page that
+//! occurs in a rule gets an index, and every page a row of bits with the
+//! pages that have to come after it. Whether one page goes before another is
+//! then a single bit.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use rayon::prelude::*;
+use common::{stream_lines, InputError};
+use crate::validation::{shortest_cycle, Unorderable};
+use crate::{read_rule, read_update};
+
+/// The rules, as a bitset of which page has to come before which.
+pub struct Precedence {
+    rules: HashMap<usize, Vec<usize>>,
+    /// The index of every page that occurs in a rule.
+    index: HashMap<usize, usize>,
+    /// How many `u64` a row of bits takes.
+    words: usize,
+    /// Row by row, for every page the pages that have to come after it.
+    after: Vec<u64>,
+}
+
+/// The middle pages of a batch of updates, added up.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Middles {
+    /// The sum over the updates that were in order already, as part 1 asks.
+    pub ordered: usize,
+    /// The sum over the updates that had to be put in order, as part 2 asks.
+    pub corrected: usize,
+    /// How many updates could not be put in order, these add to neither sum.
+    pub unorderable: usize,
+    /// The first update that could not be put in order, with its index in
+    /// the batch.
+    pub first_unorderable: Option<(usize, Unorderable)>,
+}
+
+impl Middles {
+    fn add(mut self, other: Middles) -> Middles {
+        self.ordered += other.ordered;
+        self.corrected += other.corrected;
+        self.unorderable += other.unorderable;
+        self.first_unorderable = match (self.first_unorderable, other.first_unorderable) {
+            (Some(first), Some(second)) => Some(if first.0 <= second.0 { first } else { second }),
+            (first, second) => first.or(second),
+        };
+        self
+    }
+}
+
+impl Precedence {
+    /// `rules` map every page to the pages that have to come after it.
+    pub fn new(rules: &HashMap<usize, Vec<usize>>) -> Self {
+        let mut pages: Vec<usize> = rules.iter()
+                                         .flat_map(|(before, afters)| std::iter::once(before).chain(afters))
+                                         .copied()
+                                         .collect();
+        pages.sort_unstable();
+        pages.dedup();
+        let index: HashMap<usize, usize> = pages.iter().enumerate().map(|(i, page)| (*page, i)).collect();
+
+        let words = pages.len().div_ceil(64);
+        let mut after = vec![0; pages.len() * words];
+        for (before, afters) in rules {
+            for page in afters {
+                let (row, column) = (index[before], index[page]);
+                after[row * words + column / 64] |= 1 << (column % 64);
+            }
+        }
+        Precedence { rules: rules.clone(), index, words, after }
+    }
+
+    /// Whether a rule says `before` has to come before `after`.
+    pub fn precedes(&self, before: usize, after: usize) -> bool {
+        self.bit(self.index.get(&before).copied(), self.index.get(&after).copied())
+    }
+
+    /// Whether a rule says the page with index `before` has to come before
+    /// the one with index `after`, `None` being a page no rule mentions.
+    fn bit(&self, before: Option<usize>, after: Option<usize>) -> bool {
+        match (before, after) {
+            (Some(row), Some(column)) => self.after[row * self.words + column / 64] & (1 << (column % 64)) != 0,
+            _ => false,
+        }
+    }
+
+    /// The pages that have to come after `page`, `None` for a page no rule
+    /// mentions.
+    fn row(&self, page: usize) -> Option<&[u64]> {
+        self.index.get(&page).map(|row| &self.after[row * self.words..(row + 1) * self.words])
+    }
+
+    /// Whether `update` breaks none of the rules.
+    pub fn is_ordered(&self, update: &[usize]) -> bool {
+        // The pages seen so far, none of which may have to come after a later page
+        let mut seen = vec![0; self.words];
+        for page in update {
+            if let (Some(row), Some(column)) = (self.row(*page), self.index.get(page)) {
+                if row.iter().zip(&seen).any(|(after, seen)| after & seen != 0) {
+                    return false;
+                }
+                seen[column / 64] |= 1 << (column % 64);
+            }
+        }
+        true
+    }
+
+    /// The pages of `update` in an order that satisfies the rules. When the
+    /// rules say how every two pages of the update go, sorting with them
+    /// gives that order; otherwise the pages are taken one at a time as
+    /// nothing is left that has to come before them.
+    pub fn order(&self, update: &[usize]) -> Result<Vec<usize>, Unorderable> {
+        let indices: Vec<Option<usize>> = update.iter().map(|page| self.index.get(page).copied()).collect();
+        // How many pages of the update have to come before every page
+        let waiting: Vec<usize> = indices.iter()
+                                         .map(|page| indices.iter().filter(|before| self.bit(**before, *page)).count())
+                                         .collect();
+
+        // The rules say how every two pages go, and they do not go round in
+        // a circle when one page has none of the others before it, one has
+        // one, and so on. Only then can a sort rely on them.
+        let tournament = indices.iter().enumerate().all(|(i, first)| {
+            indices[i + 1..].iter().all(|second| self.bit(*first, *second) != self.bit(*second, *first))
+        });
+        let mut counts = waiting.clone();
+        counts.sort_unstable();
+        if tournament && counts.iter().enumerate().all(|(i, count)| *count == i) {
+            let mut sorted: Vec<(Option<usize>, usize)> = indices.into_iter().zip(update.iter().copied()).collect();
+            sorted.sort_by(|(first, _), (second, _)| {
+                if first == second {
+                    Ordering::Equal
+                } else if self.bit(*first, *second) {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            });
+            return Ok(sorted.into_iter().map(|(_, page)| page).collect());
+        }
+        self.kahn(update, &indices, waiting)
+    }
+
+    /// Kahn's algorithm, taking the first page of `update` that nothing left
+    /// has to come before, again and again. `indices` holds the index of
+    /// every page, and `waiting` how many pages have to come before it.
+    fn kahn(&self, update: &[usize], indices: &[Option<usize>], mut waiting: Vec<usize>) -> Result<Vec<usize>, Unorderable> {
+        let mut taken = vec![false; update.len()];
+        let mut ordered = Vec::with_capacity(update.len());
+
+        while let Some(next) = (0..update.len()).find(|i| !taken[*i] && waiting[*i] == 0) {
+            taken[next] = true;
+            ordered.push(update[next]);
+            for (i, page) in indices.iter().enumerate() {
+                if !taken[i] && self.bit(indices[next], *page) {
+                    waiting[i] -= 1;
+                }
+            }
+        }
+
+        if ordered.len() < update.len() {
+            let stuck: HashSet<usize> = (0..update.len()).filter(|i| !taken[*i]).map(|i| update[i]).collect();
+            let cycle = shortest_cycle(&self.rules, Some(&stuck)).expect("pages waiting for one another form a cycle");
+            return Err(Unorderable { update: update.to_vec(), cycle });
+        }
+        Ok(ordered)
+    }
+
+    /// Checks and corrects all `updates`, spread over all threads.
+    pub fn middles(&self, updates: &[Vec<usize>]) -> Middles {
+        updates.par_iter()
+               .enumerate()
+               .map(|(i, update)| {
+                   let mut middles = Middles::default();
+                   if self.is_ordered(update) {
+                       middles.ordered = update[update.len() / 2];
+                   } else {
+                       match self.order(update) {
+                           Ok(ordered) => middles.corrected = ordered[ordered.len() / 2],
+                           Err(error) => {
+                               middles.unorderable = 1;
+                               middles.first_unorderable = Some((i, error));
+                           }
+                       }
+                   }
+                   middles
+               })
+               .reduce(Middles::default, Middles::add)
+    }
+}
+
+/// Checks and corrects the updates in `filename`, reading `batch` of them at
+/// a time and spreading every batch over all threads. Blank lines between
+/// the updates are skipped, as when solving the input.
+pub fn middles_file(filename: &str, batch: usize) -> Result<Middles, InputError> {
+    let mut lines = stream_lines(filename)?;
+    let mut rules: HashMap<usize, Vec<usize>> = HashMap::new();
+    for line in lines.by_ref() {
+        let line = line?;
+        if line.text.is_empty() {
+            break;
+        }
+        let (before, after) = read_rule(&line)?;
+        rules.entry(before).or_default().push(after);
+    }
+    let precedence = Precedence::new(&rules);
+
+    let mut middles = Middles::default();
+    let mut updates = Vec::with_capacity(batch);
+    let mut seen = 0;
+    loop {
+        let line = lines.next().transpose()?;
+        if let Some(line) = &line {
+            if line.text.is_empty() {
+                continue;
+            }
+            updates.push(read_update(line)?);
+        }
+        if updates.len() == batch.max(1) || (line.is_none() && !updates.is_empty()) {
+            let mut counted = precedence.middles(&updates);
+            if let Some((i, _)) = &mut counted.first_unorderable {
+                *i += seen;
+            }
+            middles = middles.add(counted);
+            seen += updates.len();
+            updates.clear();
+        }
+        if line.is_none() {
+            return Ok(middles);
+        }
+    }
+}
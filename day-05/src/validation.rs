@@ -0,0 +1,97 @@
+//! Finding page ordering rules that contradict one another.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+/// A page ordering rule: `before` has to be printed before `after`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub before: usize,
+    pub after: usize,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}|{}", self.before, self.after)
+    }
+}
+
+/// Rules that go round in a circle, so that they can not all hold. Every
+/// rule's `after` is the next rule's `before`, and the last rule's `after`
+/// is the first rule's `before`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub rules: Vec<Rule>,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<String> = self.rules.iter().map(Rule::to_string).collect();
+        write!(f, "{}", rules.join(", "))
+    }
+}
+
+/// An update whose pages no order satisfies the rules for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unorderable {
+    pub update: Vec<usize>,
+    /// The fewest rules between pages of the update that contradict one
+    /// another.
+    pub cycle: Cycle,
+}
+
+impl fmt::Display for Unorderable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pages: Vec<String> = self.update.iter().map(usize::to_string).collect();
+        write!(f, "the pages {} can not be put in order, the rules {} contradict one another", pages.join(","), self.cycle)
+    }
+}
+
+impl Error for Unorderable {}
+
+/// The shortest cycle in `rules`, which map every page to the pages that
+/// have to come after it, only looking at the rules between `pages` when
+/// given. Of the shortest cycles, the one through the lowest page wins.
+pub fn shortest_cycle(rules: &HashMap<usize, Vec<usize>>, pages: Option<&HashSet<usize>>) -> Option<Cycle> {
+    let allowed = |page: &usize| pages.is_none_or(|pages| pages.contains(page));
+    let mut starts: Vec<usize> = rules.keys().copied().filter(allowed).collect();
+    starts.sort_unstable();
+
+    let mut shortest: Option<Vec<usize>> = None;
+    for start in starts {
+        // Breadth first from `start`, until a rule leads back to it
+        let mut previous: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        let mut closing = None;
+        'search: while let Some(page) = queue.pop_front() {
+            for next in rules.get(&page).into_iter().flatten().filter(|next| allowed(next)) {
+                if *next == start {
+                    closing = Some(page);
+                    break 'search;
+                }
+                if !previous.contains_key(next) {
+                    previous.insert(*next, page);
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        let Some(mut page) = closing else {
+            continue;
+        };
+        let mut path = vec![page];
+        while page != start {
+            page = previous[&page];
+            path.push(page);
+        }
+        path.reverse();
+        if shortest.as_ref().is_none_or(|shortest| path.len() < shortest.len()) {
+            shortest = Some(path);
+        }
+    }
+
+    shortest.map(|path| Cycle {
+        rules: (0..path.len()).map(|i| Rule { before: path[i], after: path[(i + 1) % path.len()] }).collect(),
+    })
+}
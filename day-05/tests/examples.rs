@@ -0,0 +1 @@
+common::example_tests!(day_05::Day05; test);
@@ -0,0 +1,94 @@
+//! Checking whether an obstruction traps the guard without walking every
+//! step.
+//!
+//! For every cell and direction the guard can face, a jump table holds the
+//! cell where they stop in front of the next obstacle, or that they walk off
+//! the map. A walk then goes from obstacle to obstacle. An added obstruction
+//! only changes the jumps that would pass its cell, so those are cut short at
+//! it as they come up, and the table itself is shared by all candidates.
+
+use std::collections::HashSet;
+use grid::{Grid, Point};
+
+/// The directions the guard can face, a quarter turn clockwise apart,
+/// starting up.
+const DIRECTIONS: [Point; 4] = [Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)];
+
+/// Where the guard stops walking from every cell in every direction.
+pub struct Jumps {
+    width: i32,
+    height: i32,
+    start: Point,
+    /// By cell and then direction, the cell in front of the next obstacle,
+    /// `None` when the guard walks off the map first or the cell is an
+    /// obstacle itself.
+    stops: Vec<Option<Point>>,
+}
+
+impl Jumps {
+    /// The jumps on `map`, where `#` is an obstacle and the guard starts at
+    /// `start` facing up.
+    pub fn new(map: &Grid<char>, start: Point) -> Self {
+        let (width, height) = (map.width(), map.height());
+        let points: Vec<Point> = map.iter_points().collect();
+        let mut stops = vec![None; points.len() * DIRECTIONS.len()];
+        for (direction, dxdy) in DIRECTIONS.iter().enumerate() {
+            // Every cell needs the stop of the cell ahead of it first, which
+            // comes earlier row by row going up or left, later otherwise
+            let ahead_first = dxdy.x + dxdy.y < 0;
+            let order: Box<dyn Iterator<Item = &Point>> = if ahead_first { Box::new(points.iter()) } else { Box::new(points.iter().rev()) };
+            for point in order {
+                if map.get(point) == Some(&'#') {
+                    continue;
+                }
+                let ahead = point.add(dxdy);
+                stops[Self::slot(width, point, direction)] = match map.get(&ahead) {
+                    None => None,
+                    Some('#') => Some(*point),
+                    Some(_) => stops[Self::slot(width, &ahead, direction)],
+                };
+            }
+        }
+        Jumps { width, height, start, stops }
+    }
+
+    /// Where the stop for `point` facing `direction` is kept in `stops`.
+    fn slot(width: i32, point: &Point, direction: usize) -> usize {
+        (point.y * width + point.x) as usize * DIRECTIONS.len() + direction
+    }
+
+    /// Where the guard at `point` facing `direction` stops with an extra
+    /// obstacle at `obstruction`, `None` when they walk off the map.
+    fn jump(&self, point: &Point, direction: usize, obstruction: &Point) -> Option<Point> {
+        let stop = self.stops[Self::slot(self.width, point, direction)];
+        let dxdy = DIRECTIONS[direction];
+        let offset = obstruction.minus(point);
+        // How many steps ahead the obstruction is, when it is straight ahead
+        let ahead = offset.x * dxdy.x + offset.y * dxdy.y;
+        let in_line = offset.x * dxdy.y == offset.y * dxdy.x;
+        if in_line && ahead > 0 {
+            let reach = stop.map_or(self.width.max(self.height), |stop| {
+                let walked = stop.minus(point);
+                walked.x * dxdy.x + walked.y * dxdy.y
+            });
+            if ahead <= reach {
+                return Some(obstruction.minus(&dxdy));
+            }
+        }
+        stop
+    }
+
+    /// Whether an obstacle at `obstruction` makes the guard walk in a loop.
+    pub fn loops_with(&self, obstruction: &Point) -> bool {
+        let (mut point, mut direction) = (self.start, 0);
+        let mut turns: HashSet<(Point, usize)> = HashSet::new();
+        while let Some(stop) = self.jump(&point, direction, obstruction) {
+            direction = (direction + 1) % DIRECTIONS.len();
+            point = stop;
+            if !turns.insert((point, direction)) {
+                return true;
+            }
+        }
+        false
+    }
+}
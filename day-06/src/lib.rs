@@ -0,0 +1,268 @@
+pub mod jumps;
+
+use std::collections::HashSet;
+use crate::Direction::North;
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point};
+use jumps::Jumps;
+use rayon::prelude::*;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-06"
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+
+    fn record(&self, problem: &Self::Problem, part: u8) -> Option<Recording> {
+        match part {
+            1 => Some(record1(problem)),
+            _ => Some(record2(problem)),
+        }
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    problem.calculate_visited_points().len()
+}
+
+fn solve2(problem: &Problem) -> usize {
+    let jumps = problem.jumps();
+    let candidates: Vec<Point> = problem.calculate_visited_points()
+                                        .into_iter()
+                                        .filter(|point| problem.can_be_made_obstructed(point))
+                                        .collect();
+    candidates.par_iter().filter(|point| jumps.loops_with(point)).count()
+}
+
+/// The guard walking their route one step at a time.
+fn record1(problem: &Problem) -> Recording {
+    let (states, _) = problem.walk();
+    let mut cells = problem.cells();
+    let mut recorder = Recorder::new("day-06 part 1");
+
+    for (index, state) in states.iter().enumerate() {
+        if index > 0 {
+            cells.set(&states[index - 1].point, Cell::new('X', Style::Visited));
+        }
+        cells.set(&state.point, Cell::new(state.direction.symbol(), Style::Robot));
+        recorder.record(cells.clone(), format!("step {} of {}", index + 1, states.len()));
+    }
+    recorder.finish()
+}
+
+/// Every obstruction that traps the guard, in the order the original route
+/// reaches them, each with the walk into the loop it causes. Paths that are
+/// walked up and down are `|`, left and right `-`, and both `+`.
+fn record2(problem: &Problem) -> Recording {
+    let (route, _) = problem.walk();
+    let mut seen: HashSet<Point> = HashSet::new();
+    let candidates: Vec<Point> = route.iter()
+                                      .map(|state| state.point)
+                                      .filter(|point| seen.insert(*point) && problem.can_be_made_obstructed(point))
+                                      .collect();
+
+    let mut recorder = Recorder::new("day-06 part 2");
+    for candidate in candidates {
+        let obstructed = problem.make_point_obstructed(&candidate);
+        let (states, looped) = obstructed.walk();
+        if !looped {
+            continue;
+        }
+
+        let mut cells = problem.cells();
+        for state in &states {
+            let vertical = matches!(state.direction, Direction::North | Direction::South);
+            let symbol = match (cells.get(&state.point).unwrap().symbol, vertical) {
+                ('|', false) | ('-', true) | ('+', _) => '+',
+                (_, true) => '|',
+                (_, false) => '-',
+            };
+            cells.set(&state.point, Cell::new(symbol, Style::Visited));
+        }
+        cells.set(&states[0].point, Cell::new('^', Style::Robot));
+        cells.set(&candidate, Cell::new('O', Style::Marker));
+        recorder.record(cells,
+                        format!("loop {} with an obstruction at ({}, {}), after {} steps",
+                                recorder.len() + 1, candidate.x, candidate.y, states.len()));
+    }
+    recorder.finish()
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub struct Problem {
+    map: Grid<char>,
+}
+
+impl Problem {
+    fn is_obstructed(&self, point: &Point) -> bool {
+        self.map.get(point) == Some(&'#')
+    }
+
+    fn start_position(&self) -> Point {
+        self.map.find(&'^').unwrap()
+    }
+
+    fn can_be_made_obstructed(&self, point: &Point) -> bool {
+        self.map.get(point) == Some(&'.')
+    }
+
+    fn make_point_obstructed(&self, point: &Point) -> Self {
+        let mut next_problem = self.clone();
+        next_problem.map.set(point, '#');
+        next_problem
+    }
+
+    /// Where the guard stops from every cell and direction, to check
+    /// obstructions with.
+    pub fn jumps(&self) -> Jumps {
+        Jumps::new(&self.map, self.start_position())
+    }
+
+    /// The states of the guard from the start until they leave the map or are
+    /// back in a state they were in before, and whether they were.
+    fn walk(&self) -> (Vec<State>, bool) {
+        let mut state = State::new(self.start_position(), North);
+        let mut visited_states: HashSet<State> = HashSet::new();
+        let mut states: Vec<State> = Vec::new();
+
+        while self.map.is_on_map(&state.point) {
+            if !visited_states.insert(state.clone()) {
+                return (states, true);
+            }
+            states.push(state.clone());
+            let mut next_state = state.step();
+            if self.is_obstructed(&next_state.point) {
+                next_state = state.rotate()
+            }
+            state = next_state
+        }
+
+        (states, false)
+    }
+
+    /// The map as a frame of a recording, without the guard.
+    fn cells(&self) -> Grid<Cell> {
+        self.map.map(|&symbol| match symbol {
+            '#' => Cell::new('#', Style::Wall),
+            _ => Cell::FLOOR,
+        })
+    }
+
+    fn calculate_visited_points(&self) -> HashSet<Point>   {
+        let start_position = self.start_position();
+        let mut visited_positions: HashSet<Point> = vec![start_position].into_iter().collect();
+        let mut state = State::new(start_position, North);
+
+        while self.map.is_on_map(&state.point) {
+            visited_positions.insert(state.point);
+            let mut next_state = state.step();
+            if self.is_obstructed(&next_state.point) {
+                next_state = state.rotate()
+            }
+            state = next_state
+        }
+
+        visited_positions
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    fn symbol(&self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::South => 'v',
+            Direction::East => '>',
+            Direction::West => '<',
+        }
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+struct State {
+    point: Point,
+    direction: Direction
+}
+
+impl State {
+    fn new(point: Point, direction: Direction) -> Self {
+        State { point, direction }
+    }
+
+    fn step(&self) -> Self {
+        let dxdy = match self.direction {
+            Direction::North => Point::new(0, -1),
+            Direction::South => Point::new(0, 1),
+            Direction::East => Point::new(1, 0),
+            Direction::West => Point::new(-1, 0),
+        };
+        State::new(self.point.add(&dxdy), self.direction)
+    }
+
+    fn rotate(&self) -> Self {
+        let next_direction = match self.direction {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        };
+        State::new(self.point, next_direction)
+    }
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines
+        .grid_rows("`.`, `#` or `^`", |c| ".#^".contains(c).then_some(c))?;
+    Ok(Problem { map: Grid::new(map) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn jumps_find_the_loops_walking_finds() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..50 {
+            let (width, height) = (rng.random_range(1..=12), rng.random_range(1..=12));
+            let mut rows: Vec<Vec<char>> = (0..height).map(|_| (0..width).map(|_| if rng.random_range(0..5) == 0 { '#' } else { '.' }).collect())
+                                                      .collect();
+            rows[rng.random_range(0..height)][rng.random_range(0..width)] = '^';
+            let problem = Problem { map: Grid::new(rows) };
+
+            let jumps = problem.jumps();
+            for point in problem.map.iter_points().filter(|point| problem.can_be_made_obstructed(point)) {
+                assert_eq!(jumps.loops_with(&point), problem.make_point_obstructed(&point).walk().1, "{:?} in\n{}", point, problem.map);
+            }
+        }
+    }
+}
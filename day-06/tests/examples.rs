@@ -0,0 +1 @@
+common::example_tests!(day_06::Day06; test);
@@ -0,0 +1,117 @@
+use common::{Answer, Lines, ParseError, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-07"
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> isize {
+    problem.equations.iter()
+                                      .filter(|calibration| is_solvable(calibration.target,
+                                                                               calibration.components[0],
+                                                                                    &calibration.components[1..]))
+                                      .map(|calibration| {
+                                          calibration.target
+                                      })
+                                      .sum()
+}
+
+fn solve2(problem: &Problem) -> isize {
+    problem.equations.iter()
+        .filter(|calibration| is_solvable_with_third_operator(calibration.target,
+                                                                          calibration.components[0],
+                                                                          &calibration.components[1..]))
+        .map(|calibration| {
+            calibration.target
+        })
+        .sum()
+}
+
+fn is_solvable(target: isize,
+               acc: isize,
+               components: &[isize]) -> bool {
+    if target == acc && components.is_empty() {
+        return true;
+    }
+    if components.is_empty() || target < acc {
+        return false
+    }
+    let next_components = components[1..].to_vec();
+    let l = acc + components[0];
+    let r = acc * components[0];
+    is_solvable(target, l, &next_components) || (is_solvable(target, r, &next_components))
+}
+
+fn is_solvable_with_third_operator(target: isize,
+                                   acc: isize,
+                                   components: &[isize]) -> bool {
+    if target == acc && components.is_empty() {
+        return true;
+    }
+    if components.is_empty() || target < acc {
+        return false
+    }
+    let next_components = components[1..].to_vec();
+    let l = acc + components[0];
+    let r = acc * components[0];
+    let t = format!("{}{}", acc, components[0]).parse::<isize>().unwrap();
+    is_solvable_with_third_operator(target, l, &next_components)
+        || (is_solvable_with_third_operator(target, r, &next_components))
+        || (is_solvable_with_third_operator(target, t, &next_components))
+}
+
+pub struct Problem {
+    equations: Vec<Calibration>
+}
+impl Problem {
+    fn new(equations: Vec<Calibration>) -> Self {
+        Problem { equations }
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+struct Calibration {
+    target: isize,
+    components: Vec<isize>,
+}
+
+impl Calibration {
+    fn new(target: isize, components: Vec<isize>) -> Self {
+        Calibration { target, components }
+    }
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let calibrations: Vec<Calibration> =
+        lines.map(|line|  {
+            let (target, components) = line.split_once(": ")?;
+            let target = line.parse::<isize>(target, "a test value")?;
+            let components: Vec<isize> = components.split_whitespace()
+                                                   .map(|num| line.parse::<isize>(num, "a number"))
+                                                   .collect::<Result<_, _>>()?;
+            Ok(Calibration::new(target, components))
+        }).collect::<Result<_, ParseError>>()?;
+
+    Ok(Problem::new(calibrations))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_07::Day07; test, test_small);
@@ -0,0 +1,140 @@
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-08"
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    let antana_type_to_their_points = antana_type_to_their_points(problem);
+
+    problem.map.iter_points()
+           .filter(|point| is_antinode(&antana_type_to_their_points, point))
+           .count()
+}
+
+fn solve2(problem: &Problem) -> usize {
+    let antana_type_to_their_points = antana_type_to_their_points(problem);
+
+    problem.map.iter_points()
+                            .filter(|point|
+                                is_antinode_relaxed(&antana_type_to_their_points,
+                                                    point,
+                                                    problem))
+
+                            .count()
+}
+
+fn antana_type_to_their_points(problem: &Problem) -> HashMap<char, HashSet<Point>> {
+    problem.map.iter_points()
+           .filter(|point| problem.map.get(point) != Some(&'.')
+                                  && problem.map.get(point) != Some(&'#'))
+           .fold(HashMap::new(), |mut acc, point| {
+                acc.entry(problem.map.get(&point).copied().unwrap())
+                   .or_insert_with(HashSet::new).insert(point);
+                acc
+           })
+}
+
+fn is_antinode_relaxed(antana_type_to_their_points: &HashMap<char, HashSet<Point>>,
+                       point: &Point,
+                       problem: &Problem) -> bool {
+    antana_type_to_their_points.values().any(|points| {
+        points.iter()
+            .tuple_combinations() // Generate all unique pairs of points
+            .any(|(antana1, antana2)|
+                match_antinode_condition_relaxed(antana1,
+                                                 antana2,
+                                                 point,
+                                                 problem))
+    })
+}
+
+fn match_antinode_condition_relaxed(antana1: &Point,
+                                    antana2: &Point,
+                                    point: &Point,
+                                    problem: &Problem) -> bool {
+    let (mut l, mut r) = if antana1.x <= antana2.x {
+        (*antana1, *antana2)
+    } else {
+        (*antana2, *antana1)
+    };
+    let difference = r.minus(&l);
+
+    while problem.map.is_on_map(&l) {
+        if point == &l {
+            return true;
+        }
+        l = l.minus(&difference);
+    }
+
+    while problem.map.is_on_map(&r) {
+        if point == &r {
+            return true;
+        }
+        r = r.add(&difference);
+    }
+
+    false
+}
+
+
+fn is_antinode(antana_type_to_their_points: &HashMap<char, HashSet<Point>>,
+               point: &Point) -> bool {
+    antana_type_to_their_points.values().any(|points| {
+        points.iter()
+              .tuple_combinations() // Generate all unique pairs of points
+              .any(|(antana1, antana2)| match_antinode_condition(antana1, antana2, point))
+    })
+}
+
+fn match_antinode_condition(antana1: &Point,
+                            antana2: &Point,
+                            point: &Point) -> bool {
+    let (l, r) = if antana1.x <= antana2.x {
+        (antana1, antana2)
+    } else {
+        (antana2, antana1)
+    };
+    let difference = r.minus(l);
+    let l_antinode = l.minus(&difference);
+    let r_antinode = r.add(&difference);
+    point == &l_antinode || point == &r_antinode
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    map: Grid<char>
+}
+
+impl Problem {
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines.grid_rows("an antenna or `.`", Some)?;
+    Ok(Problem { map: Grid::new(map) })
+}
\ No newline at end of file
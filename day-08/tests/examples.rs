@@ -0,0 +1 @@
+common::example_tests!(day_08::Day08; test, test2);
@@ -0,0 +1 @@
+common::example_tests!(day_09_part2::Day09Part2; test, test_small);
@@ -0,0 +1 @@
+common::example_tests!(day_09::Day09; test, test_small);
@@ -0,0 +1,75 @@
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point};
+use search::Paths;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-10"
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    trail_heads(problem).map(|trails| trail_ends(problem, &trails).count())
+                        .sum()
+}
+
+fn solve2(problem: &Problem) -> usize {
+    trail_heads(problem).map(|trails| trail_ends(problem, &trails).map(|end| trails.count_optimal_paths(&end))
+                                                                  .sum::<usize>())
+                        .sum()
+}
+
+/// All trails from every trail head. Every trail climbs one step at a time,
+/// so every trail to a cell is as short as the shortest one.
+fn trail_heads(problem: &Problem) -> impl Iterator<Item = Paths<Point, usize>> + '_ {
+    problem.map.iter_points()
+               .filter(|point| problem.map.get(point) == Some(&0))
+               .map(|start_pos| search::bfs(start_pos, |point| {
+                   let next_height = problem.map.get(point).copied().unwrap() + 1;
+                   problem.map.neighbours4(point)
+                              .filter(|next_point| problem.map.get(next_point) == Some(&next_height))
+                              .collect::<Vec<_>>()
+               }))
+}
+
+fn trail_ends<'a>(problem: &'a Problem, trails: &'a Paths<Point, usize>) -> impl Iterator<Item = Point> + 'a {
+    trails.iter()
+          .map(|(point, _)| *point)
+          .filter(|point| problem.map.get(point) == Some(&9))
+}
+
+pub struct Problem {
+    map: Grid<usize>,
+}
+
+impl Problem {
+    fn new(map: Grid<usize>) -> Self {
+        Problem { map}
+    }
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines
+        .grid_rows("a height from 0 to 9", |c| c.to_digit(10).map(|height| height as usize))?;
+    Ok(Problem::new(Grid::new(map)))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_10::Day10; test, test_small);
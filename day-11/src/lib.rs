@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use common::{Answer, InputError, Lines, Param, Params, ParseError, Solution};
+
+pub struct Day11;
+
+const PARAMS: &[Param] = &[
+    Param::new("blinks1", 25, "times to blink in part 1"),
+    Param::new("blinks2", 75, "times to blink in part 2"),
+];
+
+impl Solution for Day11 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-11"
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn configure(&self, problem: &mut Self::Problem, params: &Params) -> Result<(), InputError> {
+        problem.blinks = [params.get("blinks1"), params.get("blinks2")];
+        Ok(())
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(&problem.stones, problem.blinks[0]).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(&problem.stones, problem.blinks[1]).into())
+    }
+}
+
+pub struct Problem {
+    stones: Vec<usize>,
+    blinks: [usize; 2],
+}
+
+fn solve(input: &[usize], amount: usize) -> usize {
+    let mut cache:  HashMap<(usize, usize), usize> = HashMap::new();
+
+    input.iter()
+            .map(|stone_number|
+                do_solve(*stone_number, 0, amount, &mut cache))
+            .sum::<usize>()
+}
+
+fn do_solve(stone_number: usize,
+            current_blink: usize,
+            max_depth: usize,
+            cache: &mut HashMap<(usize, usize), usize>) -> usize {
+
+    let key = (stone_number, current_blink);
+    if cache.contains_key(&key) {
+        return *cache.get(&key).unwrap();
+    }
+
+    let stone_number_str = stone_number.to_string();
+    let res =
+        if current_blink == max_depth {
+            1
+        } else if stone_number == 0  {
+            do_solve(1, current_blink + 1, max_depth, cache)
+        } else if stone_number_str.len().is_multiple_of(2)  {
+            let midpoint = stone_number_str.len() / 2;
+            let (first_half, second_half) = stone_number_str.split_at(midpoint);
+
+            let first_half = first_half.parse::<usize>().unwrap();
+            let second_half = second_half.parse::<usize>().unwrap();
+
+            let first = do_solve(first_half, current_blink + 1, max_depth, cache);
+            let second = do_solve(second_half, current_blink + 1, max_depth, cache);
+            first + second
+
+        }  else {
+            do_solve(stone_number * 2024, current_blink + 1, max_depth, cache)
+        };
+    cache.insert(key, res);
+    res
+}
+
+
+fn read_input(mut lines: Lines) -> Result<Problem, ParseError> {
+    let line = lines.expect("a line of stones")?;
+
+    let input =
+        line.text.split_whitespace()
+            .map(|amount| line.parse::<usize>(amount, "a stone number"))
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
+    // The blinks are set by `configure`
+    Ok(Problem { stones: input, blinks: [0, 0] })
+}
@@ -0,0 +1 @@
+common::example_tests!(day_11::Day11; test);
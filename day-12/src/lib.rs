@@ -0,0 +1,288 @@
+use std::collections::{HashSet, VecDeque};
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-12"
+    }
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+
+    fn record(&self, problem: &Self::Problem, _part: u8) -> Option<Recording> {
+        Some(record(problem))
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    problem.find_regions().iter().map(calculate_fencing).sum::<usize>()
+}
+
+fn solve2(problem: &Problem) -> usize {
+    problem.find_regions().iter().map(calculate_fencing_bulk).sum::<usize>()
+}
+
+/// The garden with every region in its own colour. Both parts fence the same
+/// regions, so they share the picture.
+fn record(problem: &Problem) -> Recording {
+    let regions = problem.find_regions();
+    let mut region_of: Grid<usize> = problem.map.map(|_| 0);
+    for (index, region) in regions.iter().enumerate() {
+        region.iter().for_each(|point| region_of.set(point, index));
+    }
+
+    // Greedily gives every region the first colour none of its earlier neighbours has
+    let mut colours: Vec<usize> = Vec::new();
+    for (index, region) in regions.iter().enumerate() {
+        let taken: HashSet<usize> =
+            region.iter()
+                  .flat_map(|point| region_of.neighbours4(point).collect::<Vec<_>>())
+                  .map(|neighbour| *region_of.get(&neighbour).unwrap())
+                  .filter(|other| *other < index)
+                  .map(|other| colours[other])
+                  .collect();
+        colours.push((0..).find(|colour| !taken.contains(colour)).unwrap());
+    }
+
+    let mut cells = problem.map.map(|plant| Cell::new(*plant, Style::Floor));
+    for (point, region) in region_of.iter() {
+        cells.set(&point, Cell::new(*problem.map.get(&point).unwrap(), Style::Region(colours[*region])));
+    }
+
+    let mut recorder = Recorder::new("day-12");
+    recorder.record(cells, format!("{} regions", regions.len()));
+    recorder.finish()
+}
+
+fn calculate_fencing(region: &HashSet<Point>) -> usize {
+
+    let dxdys =
+        [Point::new(-1, 0), Point::new(1, 0), Point::new(0, -1), Point::new(0, 1)];
+
+    let area = region.len();
+
+    let perimeter =
+        region.iter()
+              .map(|point|{
+                   dxdys.iter()
+                        .filter(|dxdy| !region.contains(&point.add(dxdy)))
+                        .count()
+              })
+            .sum::<usize>();
+
+    perimeter * area
+}
+
+// 799438 too low
+
+fn calculate_fencing_bulk(region: &HashSet<Point>) -> usize {
+    let area = region.len();
+
+    let mut vertical_border_edges : HashSet<Edge> = HashSet::new();
+    let mut horizontal_border_edges : HashSet<Edge> = HashSet::new();
+    let mut horizontal_blocked_points : HashSet<Point> = HashSet::new();
+    let mut vertically_blocked_points : HashSet<Point> = HashSet::new();
+
+    region.iter().for_each(|point|{
+        let left = point.add(&Point::new(-1, 0));
+        if !region.contains(&left) {
+            let (l, r)  = (Point::new(point.x, point.y),
+                                       Point::new(point.x, point.y + 1));
+            let edge =  (l, r);
+            vertical_border_edges.insert(edge);
+            horizontal_blocked_points.insert(l);
+            horizontal_blocked_points.insert(r);
+        }
+        let right = point.add(&Point::new(1, 0));
+        if !region.contains(&right) {
+            let (l,r ) = (Point::new(point.x + 1, point.y),
+                          Point::new(point.x + 1, point.y + 1));
+            let edge = (l, r);
+            vertical_border_edges.insert(edge);
+            horizontal_blocked_points.insert(l);
+            horizontal_blocked_points.insert(r);
+        }
+
+        let up = point.add(&Point::new(0, -1));
+        if !region.contains(&up) {
+            let (l,r ) =  (Point::new(point.x, point.y),
+                                       Point::new(point.x + 1, point.y));
+            let edge = (l, r);
+            horizontal_border_edges.insert(edge);
+            vertically_blocked_points.insert(l);
+            vertically_blocked_points.insert(r);
+        }
+        let down = point.add(&Point::new(0, 1));
+        if !region.contains(&down) {
+            let (l, r) = (Point::new(point.x, point.y + 1),
+                                      Point::new(point.x + 1, point.y + 1));
+            let edge = (l, r);
+            horizontal_border_edges.insert(edge);
+            vertically_blocked_points.insert(l);
+            vertically_blocked_points.insert(r);
+        }
+    });
+
+
+    let mut sides = 0;
+
+    let min_x = region.iter().map(|point| point.x).min().unwrap();
+    let max_x = region.iter().map(|point| point.x).max().unwrap();
+    let min_y = region.iter().map(|point| point.y).min().unwrap();
+    let max_y = region.iter().map(|point| point.y).max().unwrap();
+
+    for vertical_edge_x_coordinate in  min_x..(max_x+2) {
+        let mut vertical_edges: Vec<Edge> =
+            vertical_border_edges.iter()
+                                 .filter(|edge| edge.0.x == vertical_edge_x_coordinate).cloned()
+                                 .collect();
+
+        vertical_edges.sort_by_key(|l| l.0.y);
+
+        if vertical_edges.is_empty() {
+            continue
+        }
+
+        let amount_of_gaps = vertical_edges.windows(2)
+                                                 .filter(|pair|
+                                                            pair[1].0.y != pair[0].1.y
+                                                            || vertically_blocked_points.contains(&pair[1].0))
+                                                 .count();
+        sides += amount_of_gaps + 1
+
+    }
+
+    for horizontal_edge_y_coordinate in  min_y..(max_y+2) {
+        let mut horizontal_edges: Vec<Edge>  =
+            horizontal_border_edges.iter()
+                                   .filter(|edge| edge.0.y == horizontal_edge_y_coordinate).cloned()
+                                   .collect();
+
+        horizontal_edges.sort_by_key(|l| l.0.x);
+
+        if horizontal_edges.is_empty() {
+            continue
+        }
+
+        let amount_of_gaps =
+            horizontal_edges.windows(2)
+                            .filter(|pair|
+                                pair[1].0.x != pair[0].1.x
+                                || horizontal_blocked_points.contains(&pair[1].0))
+                            .count();
+
+        sides += amount_of_gaps + 1
+    }
+    area * sides
+}
+
+
+pub struct Problem {
+    map: Grid<char>,
+}
+
+impl Problem {
+    fn new(map: Grid<char>) -> Self {
+        Problem { map}
+    }
+
+    fn find_regions(&self) -> Vec<HashSet<Point>> {
+        let mut regions: Vec<HashSet<Point>> = Vec::new();
+
+        for point in self.map.iter_points() {
+            if regions.iter().any(|set| set.contains(&point)) {
+                continue;
+            }
+            regions.push(self.find_region(&point));
+        }
+
+        regions
+    }
+
+    fn find_region(&self, point: &Point) -> HashSet<Point> {
+        let mut points = HashSet::new();
+
+        let mut queue: VecDeque<Point> = VecDeque::new();
+
+        queue.push_back(*point);
+        while !queue.is_empty() {
+            let current_point = queue.pop_front().unwrap();
+            if points.contains(&current_point) {
+                continue;
+            }
+            points.insert(current_point);
+            self.map.neighbours4(&current_point)
+                .filter(|next_point| {
+                    self.map.get(point) == self.map.get(next_point)
+                    && !points.contains(next_point)
+                }).for_each(|np| {
+                queue.push_back(np)
+            });
+        }
+
+        points
+    }
+}
+
+type Edge = (Point, Point);
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines.grid_rows("a plant", |c| c.is_alphabetic().then_some(c))?;
+    Ok(Problem::new(Grid::new(map)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(rows: &[&str], plant: char) -> HashSet<Point> {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                   .enumerate()
+                   .filter(move |(_, c)| *c == plant)
+                   .map(move |(x, _)| Point::new(x as i32, y as i32))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bulk_fencing_of_a_single_plot_has_four_sides() {
+        assert_eq!(calculate_fencing_bulk(&region(&["A"], 'A')), 4);
+    }
+
+    #[test]
+    fn bulk_fencing_counts_sides_instead_of_edges() {
+        // The E shaped region from the puzzle: area 17 with 12 sides
+        let rows = ["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"];
+        assert_eq!(calculate_fencing_bulk(&region(&rows, 'E')), 17 * 12);
+    }
+
+    #[test]
+    fn bulk_fencing_counts_the_sides_of_holes() {
+        // The region around the two B regions touching at a corner: area 28 with 12 sides
+        let rows = ["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"];
+        assert_eq!(calculate_fencing_bulk(&region(&rows, 'A')), 28 * 12);
+    }
+}
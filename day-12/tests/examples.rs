@@ -0,0 +1 @@
+common::example_tests!(day_12::Day12; test, test2, test3, test4, test5);
@@ -0,0 +1,189 @@
+use regex::Regex;
+use common::{Answer, Lines, ParseError, Solution};
+use common::input::Line;
+use grid::Point;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-13"
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    problem.claw_machines.iter()
+                             .filter_map(solve_claw_machine)
+                             .sum::<usize>()
+}
+
+fn solve2(problem: &Problem) -> i64 {
+    problem.claw_machines.iter()
+            .filter_map(solve_claw_machine_extended)
+            .sum::<i64>()
+}
+
+fn solve_claw_machine(claw_machine: &ClawMachine) -> Option<usize> {
+
+    let cost_a: usize = 3;
+    let cost_b: usize = 1;
+    let mut found_min: Option<usize> = None;
+
+    for i in 0..101 {
+        for j in  0..(i+1) {
+
+            let option_a =
+                claw_machine.a.mul(i).add(&claw_machine.b.mul(j));
+
+            let option_b =
+                claw_machine.a.mul(j).add(&claw_machine.b.mul(i));
+
+            if option_a  == claw_machine.prize_location {
+                let candidate = cost_a * (i as usize) + cost_b * (j as usize);
+                if found_min.is_none() || candidate < found_min.unwrap() {
+                    found_min = Some(candidate);
+                }
+
+            }
+
+            if option_b == claw_machine.prize_location {
+                let candidate = cost_a * (j as usize) + cost_b * (i as usize);
+                if found_min.is_none() || candidate < found_min.unwrap() {
+                    found_min = Some(candidate);
+                }
+            }
+        }
+    }
+    found_min
+}
+
+fn solve_claw_machine_extended(claw_machine: &ClawMachine) -> Option<i64> {
+    // I really, really hate math, especially linear algebra.
+
+    let increase: i64 = 10000000000000;
+    let target_x = i64::from(claw_machine.prize_location.x) + increase;
+    let target_y = i64::from(claw_machine.prize_location.y) + increase;
+    let (a_x, a_y) = (i64::from(claw_machine.a.x), i64::from(claw_machine.a.y));
+    let (b_x, b_y) = (i64::from(claw_machine.b.x), i64::from(claw_machine.b.y));
+
+    let determinant = determinant(claw_machine)?;
+
+    let a = divide_if_divisable((b_y * target_x) - (b_x * target_y), determinant)?;
+    let b  = divide_if_divisable(-a_y * target_x + a_x * target_y, determinant)?;
+
+    if a < 0 || b < 0 {
+        None
+    } else {
+        Some(3 * a + b)
+    }
+}
+
+fn determinant(claw_machine: &ClawMachine) -> Option<i64> {
+    let a  = i64::from(claw_machine.a.x);
+    let c = i64::from(claw_machine.a.y);
+    let b  = i64::from(claw_machine.b.x);
+    let d = i64::from(claw_machine.b.y);
+
+    let determinant  = (a * d) - (b * c);
+    if  determinant == 0 {
+        None
+    } else {
+        Some(determinant)
+    }
+}
+
+fn divide_if_divisable(nominator: i64, divisor: i64) -> Option<i64> {
+    if nominator % divisor == 0 {
+        Some(nominator / divisor)
+    } else {
+        None
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub struct Problem {
+    claw_machines: Vec<ClawMachine>
+}
+
+impl Problem {
+    fn new(claw_machines: Vec<ClawMachine>) -> Self {
+        Problem { claw_machines }
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+struct ClawMachine {
+    a: Point,
+    b: Point,
+    prize_location: Point,
+}
+
+impl ClawMachine {
+    fn new(a: Point, b: Point, prize_location: Point) -> Self {
+        ClawMachine {
+            a,
+            b,
+            prize_location,
+        }
+    }
+}
+
+fn read_input(mut it: Lines) -> Result<Problem, ParseError> {
+    let mut claw_machines: Vec<ClawMachine> = Vec::new();
+    let re = Regex::new(r"X[+=](\d+), Y[+=](\d+)$").unwrap();
+    let read_point = |line: Line| -> Result<Point, ParseError> {
+        let captures = re.captures(line.text.as_str())
+                         .ok_or_else(|| line.error(1, "`X+<number>, Y+<number>` or `X=<number>, Y=<number>`"))?;
+        Ok(Point::new(line.parse(captures.get(1).unwrap().as_str(), "a number")?,
+                      line.parse(captures.get(2).unwrap().as_str(), "a number")?))
+    };
+    loop {
+        let a = read_point(it.expect("the behavior of button A")?)?;
+        let b = read_point(it.expect("the behavior of button B")?)?;
+        let prize_location = read_point(it.expect("the location of the prize")?)?;
+        claw_machines.push(ClawMachine::new(a, b, prize_location));
+
+        if it.next().is_none() { break; }
+    }
+    Ok(Problem::new(claw_machines))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn determinant_of_independent_buttons() {
+        let claw_machine = ClawMachine::new(Point::new(94, 34),
+                                            Point::new(22, 67),
+                                            Point::new(8400, 5400));
+        assert_eq!(determinant(&claw_machine), Some(94 * 67 - 22 * 34));
+    }
+
+    #[test]
+    fn determinant_of_parallel_buttons_is_none() {
+        let claw_machine = ClawMachine::new(Point::new(1, 2),
+                                            Point::new(2, 4),
+                                            Point::new(10, 20));
+        assert_eq!(determinant(&claw_machine), None);
+    }
+}
@@ -0,0 +1 @@
+common::example_tests!(day_13::Day13; test, test_small);
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+use regex::Regex;
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, InputError, Lines, Param, Params, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day14;
+
+const PARAMS: &[Param] = &[
+    Param::new("width", 101, "width of the space the robots move in").at_least(1),
+    Param::new("height", 103, "height of the space the robots move in").at_least(1),
+    Param::new("seconds", 100, "seconds to simulate for part 1"),
+    Param::new("threshold", 32, "robots on a single row that make up the tree in part 2"),
+];
+
+impl Solution for Day14 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-14"
+    }
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn configure(&self, problem: &mut Self::Problem, params: &Params) -> Result<(), InputError> {
+        problem.width = coordinate(params, "width")?;
+        problem.height = coordinate(params, "height")?;
+        problem.seconds = params.get("seconds");
+        problem.threshold = params.get("threshold");
+        Ok(())
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(&mut problem.clone()).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        solve2(&mut problem.clone()).map(Answer::from)
+    }
+
+    fn record(&self, problem: &Self::Problem, part: u8) -> Option<Recording> {
+        Some(record(&mut problem.clone(), part))
+    }
+}
+
+/// A size of the space as the robots' coordinates hold it.
+fn coordinate(params: &Params, name: &str) -> Result<i32, InputError> {
+    let value = params.get(name);
+    i32::try_from(value).map_err(|_| InputError::Params(format!("`{}` must be at most {}, found {}", name, i32::MAX, value)))
+}
+
+fn solve1(problem: &mut Problem) -> usize {
+
+    (0..problem.seconds).for_each(|_| {
+        problem.step();
+    });
+
+    problem.safety_factor()
+}
+
+fn solve2(problem: &mut Problem) -> Option<usize> {
+    let mut res: usize = 0;
+
+    let mut seen_states: HashSet<Vec<Point>> = HashSet::new();
+
+    let x = problem.threshold;
+    loop {
+        let most_online = problem.most_on_line();
+
+        let state: Vec<Point> = problem.robots.iter().map(|r| r.position).collect();
+
+        if seen_states.contains(&state) {
+            log::debug!("We're looping after {} steps without finding the tree", res);
+            return None;
+        }
+        seen_states.insert(state);
+
+        if most_online >= x  /* problem.robots.len() / 3*/  {
+            log::debug!("{} robots on a single line after {} steps", most_online, res);
+            return Some(res);
+        }
+
+        problem.step();
+        res += 1;
+    }
+}
+
+/// The robots moving for as many seconds as part 1 simulates, or until part 2
+/// spots the tree.
+fn record(problem: &mut Problem, part: u8) -> Recording {
+    let seconds = match part {
+        1 => problem.seconds,
+        // Without a tree the robots are back where they started after at most width * height seconds
+        _ => solve2(&mut problem.clone()).unwrap_or((problem.width * problem.height) as usize),
+    };
+
+    let mut recorder = Recorder::new(format!("day-14 part {}", part));
+    recorder.record(problem.cells(), "second 0");
+    for second in 1..=seconds {
+        problem.step();
+        recorder.record(problem.cells(), format!("second {}", second));
+    }
+    recorder.finish()
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    robots: Vec<Robot>,
+    width: i32,
+    height: i32,
+    seconds: usize,
+    threshold: usize,
+}
+
+impl Problem {
+    fn new(robots: Vec<Robot>) -> Self {
+        // The size of the space and the number of seconds are set by `configure`
+        Problem { robots, width: 0, height: 0, seconds: 0, threshold: 0 }
+    }
+
+    fn step(&mut self) {
+        self.robots =
+            self.robots.iter()
+                .map(|robot| robot.move_modulo(self.width,
+                                                       self.height))
+                .collect::<Vec<Robot>>()
+    }
+
+    /// The space as a frame of a recording, showing how many robots are on
+    /// every tile.
+    fn cells(&self) -> Grid<Cell> {
+        let mut counts = Grid::filled(self.width as usize, self.height as usize, 0u32);
+        for robot in &self.robots {
+            *counts.get_mut(&robot.position).unwrap() += 1;
+        }
+        counts.map(|&count| match count {
+            0 => Cell::FLOOR,
+            _ => Cell::new(char::from_digit(count, 10).unwrap_or('#'), Style::Robot),
+        })
+    }
+
+    fn most_on_line(&self) -> usize {
+        (0..(self.height)).map(|x|self.points_on_row(x).len()).max().unwrap()
+    }
+
+    fn points_on_row(&self, row: i32) -> Vec<i32> {
+        Vec::from_iter((0..self.width).filter(|x|
+            self.robots.iter().any(|robot| robot.position == Point::new(*x, row))
+        ))
+    }
+
+    fn safety_factor(&self) -> usize {
+        let mid_x = self.width / 2;
+        let mid_y = self.height / 2;
+
+        let lower_left: Square = (Point::new(0, self.height -1),
+                                  Point::new(mid_x -1, mid_y + 1));
+
+        let upper_left: Square = (Point::new(0, mid_y -1),
+                                  Point::new(mid_x -1, 0));
+
+
+        let lower_right: Square = (Point::new(mid_x + 1, self.height -1),
+                                   Point::new(self.width -1, mid_y + 1));
+
+        let upper_right: Square = (Point::new(mid_x + 1, mid_y -1),
+                                   Point::new(self.width - 1, 0));
+
+        self.robots.iter().filter(|robot| robot.is_inside(&lower_left)).count()
+            * self.robots.iter().filter(|robot| robot.is_inside(&lower_right)).count()
+            * self.robots.iter().filter(|robot| robot.is_inside(&upper_left)).count()
+            * self.robots.iter().filter(|robot| robot.is_inside(&upper_right)).count()
+    }
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+struct Robot {
+    position: Point,
+    velocity: Point,
+}
+
+impl Robot {
+    fn new(position: Point, velocity: Point) -> Self {
+        Robot { position, velocity }
+    }
+
+    fn move_modulo(&self, max_x: i32, max_y: i32) -> Self {
+        let mut next_x = self.position.x + self.velocity.x;
+        if next_x >= max_x {
+            next_x -= max_x ;
+        } else if next_x < 0 {
+            next_x = max_x - next_x.abs()
+        }
+        let mut next_y = self.position.y + self.velocity.y;
+        if next_y >= max_y {
+            next_y -= max_y;
+        } else if next_y < 0 {
+            next_y = max_y - next_y.abs()
+        }
+
+        Robot::new(Point::new(next_x, next_y), self.velocity)
+    }
+
+    fn is_inside(&self, square: &Square) -> bool {
+        let (lower_left, upper_right) = square;
+        self.position.x >= lower_left.x && self.position.x <= upper_right.x
+        && self.position.y <= lower_left.y && self.position.y >= upper_right.y
+    }
+}
+
+type Square = (Point, Point);
+
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let re = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
+
+    let robots =
+        lines.map(|line| {
+                                   let captures =
+                                       re.captures(line.text.as_str())
+                                         .ok_or_else(|| line.error(1, "`p=<x>,<y> v=<dx>,<dy>`"))?;
+                                   let number = |index: usize| {
+                                       line.parse::<i32>(captures.get(index).unwrap().as_str(), "a number")
+                                   };
+                                   let position = Point::new(number(1)?, number(2)?);
+                                   let velocity = Point::new(number(3)?, number(4)?);
+                                   Ok(Robot::new(position, velocity))
+                               } )
+                               .collect::<Result<Vec<Robot>, ParseError>>()?;
+
+    Ok(Problem::new(robots))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_14::Day14; test);
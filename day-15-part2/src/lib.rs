@@ -0,0 +1,263 @@
+use std::collections::{HashSet, VecDeque};
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point, DXDYS4};
+
+pub struct Day15Part2;
+
+impl Solution for Day15Part2 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-15-part2"
+    }
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[2]
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(problem.clone()).into())
+    }
+
+    fn record(&self, problem: &Self::Problem, _part: u8) -> Option<Recording> {
+        Some(record(problem.clone()))
+    }
+}
+
+fn solve(mut problem: Problem) -> usize {
+    problem.run_instructions();
+    problem.sum_of_gps_coordinates()
+}
+
+fn record(mut problem: Problem) -> Recording {
+    let mut recorder = Recorder::new("day-15 part 2");
+    let mut visited: HashSet<Point> = HashSet::from([problem.robot]);
+    recorder.record(problem.cells(&visited), "start");
+
+    let instructions = problem.instructions.clone();
+    for (index, instruction) in instructions.iter().enumerate() {
+        problem.do_step(instruction);
+        visited.insert(problem.robot);
+        recorder.record(problem.cells(&visited),
+                        format!("move {} of {}: {}", index + 1, instructions.len(), instruction));
+    }
+    recorder.finish()
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    left_half_boxes: HashSet<Point>,
+
+    right_half_boxes: HashSet<Point>,
+
+    walls: HashSet<Point>,
+
+    robot: Point,
+
+    instructions: Vec<char>,
+}
+
+impl Problem {
+    fn new(left_half_boxes: HashSet<Point>,
+           right_half_boxes: HashSet<Point>,
+           walls: HashSet<Point>,
+           robot: Point,
+           instructions: Vec<char>) -> Self {
+        Problem {
+            left_half_boxes,
+            right_half_boxes,
+            walls,
+            robot,
+            instructions,
+        }
+    }
+
+    fn char_at(&self, point: &Point) -> char {
+        let is_robot = point == &self.robot;
+        let is_wall = self.walls.contains(point);
+        let is_left_box = self.left_half_boxes.contains(point);
+        let is_right_box = self.right_half_boxes.contains(point);
+        assert!(
+            [is_robot, is_wall, is_left_box, is_right_box].iter().filter(|&&x| x).count() <= 1,
+            "At most one of the booleans may be true, but this condition was violated!"
+        );
+        if is_left_box {
+            '['
+        } else if is_right_box {
+            ']'
+        } else if is_wall {
+            '#'
+        } else if is_robot {
+            '@'
+        } else {
+            '.'
+        }
+    }
+
+    /// The warehouse as a frame of a recording, with the floor the robot
+    /// stood on marked as `visited`.
+    fn cells(&self, visited: &HashSet<Point>) -> Grid<Cell> {
+        let width = self.walls.iter().map(|p| p.x).max().unwrap() + 1;
+        let height = self.walls.iter().map(|p| p.y).max().unwrap() + 1;
+
+        let rows = (0..height).map(|y| {
+            (0..width).map(|x| {
+                let point = Point::new(x, y);
+                let symbol = self.char_at(&point);
+                let style = match symbol {
+                    '#' => Style::Wall,
+                    '@' => Style::Robot,
+                    '.' if visited.contains(&point) => Style::Visited,
+                    '.' => Style::Floor,
+                    _ => Style::Box,
+                };
+                Cell::new(symbol, style)
+            }).collect()
+        }).collect();
+        Grid::new(rows)
+    }
+
+    fn sum_of_gps_coordinates(&self) -> usize {
+        self.left_half_boxes.iter().map( |p| {
+            ((100 * p.y) + p.x) as usize
+        }).sum()
+    }
+
+    fn run_instructions(&mut self) {
+        self.instructions.clone().iter().for_each(|instruction| {
+            self.do_step(instruction);
+        });
+    }
+
+    fn do_step(&mut self, instruction: &char) {
+
+        let mut effected_boxes: Vec<Box> = Vec::new();
+
+        // The moves in the order of the offsets in `DXDYS4`
+        let dxdy = match "<>^v".find(*instruction) {
+            Some(direction) => DXDYS4[direction],
+            None => panic!("Unknown instruction {}", instruction),
+        };
+
+        let mut queue: VecDeque<Point> = VecDeque::new();
+        queue.push_back(self.robot.add(&dxdy));
+
+        while let Some(point) = queue.pop_front() {
+            let next_char = self.char_at(&point);
+            match next_char {
+                '[' => {
+                    let right_point = point.add(&Point::new(1, 0));
+                    let next_box = (point, right_point);
+                    effected_boxes.push(next_box);
+                    match instruction {
+                        '^' | 'v' => {
+                            queue.push_back(point.add(&dxdy));
+                            queue.push_back(right_point.add(&dxdy));
+                        },
+                        '>' => {
+                            queue.push_back(right_point.add(&dxdy));
+                        },
+                        '<' => panic!("Can't push from the left to char"),
+                        _ => panic!("Unknown instruction {}", instruction),
+                    };
+                },
+                ']' => {
+                    let left_point = point.add(&Point::new(-1, 0));
+                    let next_box = (left_point, point);
+                    effected_boxes.push(next_box);
+                    match instruction {
+                        '^' | 'v' => {
+                            queue.push_back(left_point.add(&dxdy));
+                            queue.push_back(point.add(&dxdy));
+                        },
+                        '>' => {
+                            panic!("Can't push from the right to char")
+                        },
+                        '<' => {
+                            queue.push_back(left_point.add(&dxdy));
+                        }
+                        _ => panic!("Unknown instruction {}", instruction),
+                    };
+                },
+                '#' => {
+                    return
+                },
+                '.' => {},
+                _ => panic!("Unknown character {}", next_char),
+            }
+        }
+
+        // We can push everything update flow
+        self.robot = self.robot.add(&dxdy);
+        effected_boxes.iter().for_each(|effected_box| {
+            self.left_half_boxes.remove(&effected_box.0);
+            self.right_half_boxes.remove(&effected_box.1);
+        });
+        effected_boxes.iter().for_each(|effected_box| {
+            self.left_half_boxes.insert(effected_box.0.add(&dxdy));
+            self.right_half_boxes.insert(effected_box.1.add(&dxdy));
+        });
+
+    }
+
+}
+
+type Box = (Point, Point);
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let mut parsing_maze = true;
+    let mut y = 0;
+
+    let mut left_half_boxes: HashSet<Point> = HashSet::new();
+
+    let mut right_half_boxes: HashSet<Point> = HashSet::new();
+
+    let mut  walls: HashSet<Point> = HashSet::new();
+
+    let mut  robot: Point = Point::new(0, 0);
+
+    let mut instructions: Vec<char> = Vec::new();
+
+    for line in lines {
+        if line.text.is_empty() {
+            parsing_maze = false;
+            continue;
+        }
+        if parsing_maze {
+            let cells = line.parse_chars("`#`, `O`, `@` or `.`", |c| "#O@.".contains(c).then_some(c))?;
+            cells.into_iter().enumerate().for_each(|(index, input_char)| {
+                let left_point = Point::new((index * 2) as i32, y);
+                let right_point = Point::new((index * 2 + 1) as i32, y);
+
+                match input_char {
+                    'O' => {
+                        left_half_boxes.insert(left_point);
+                        right_half_boxes.insert(right_point);
+                    },
+                    '#' => {
+                        walls.insert(left_point);
+                        walls.insert(right_point);
+                    },
+                    '@' => { robot = left_point; },
+                    _ => {},
+                }
+            });
+            y += 1;
+        } else {
+            let mut next_instructions =
+                line.parse_chars("a move (`^`, `v`, `<` or `>`)", |c| "^v<>".contains(c).then_some(c))?;
+            instructions.append(&mut next_instructions);
+        }
+    }
+
+    Ok(Problem::new(left_half_boxes, right_half_boxes, walls, robot, instructions))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_15_part2::Day15Part2; test, test_small);
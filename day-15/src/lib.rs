@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point, DXDYS4};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-15"
+    }
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1]
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(problem.clone()).into())
+    }
+
+    fn record(&self, problem: &Self::Problem, _part: u8) -> Option<Recording> {
+        Some(record(problem.clone()))
+    }
+}
+
+fn solve(mut problem: Problem) -> usize {
+    problem.run_instructions();
+    problem.sum_of_gps_coordinates()
+}
+
+fn record(mut problem: Problem) -> Recording {
+    let mut recorder = Recorder::new("day-15 part 1");
+    let mut visited: HashSet<Point> = HashSet::from([problem.robot]);
+    recorder.record(problem.cells(&visited), "start");
+
+    let instructions = problem.instructions.clone();
+    for (index, instruction) in instructions.iter().enumerate() {
+        problem.do_step(instruction);
+        visited.insert(problem.robot);
+        recorder.record(problem.cells(&visited),
+                        format!("move {} of {}: {}", index + 1, instructions.len(), instruction));
+    }
+    recorder.finish()
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    boxes: HashSet<Point>,
+
+    walls: HashSet<Point>,
+
+    robot: Point,
+
+    instructions: Vec<char>,
+}
+
+impl Problem {
+    fn new(boxes: HashSet<Point>,
+           walls: HashSet<Point>,
+           robot: Point,
+           instructions: Vec<char>) -> Self {
+        Problem {
+            boxes,
+            walls,
+            robot,
+            instructions,
+        }
+    }
+
+    fn char_at(&self, point: &Point) -> char {
+        let is_robot = point == &self.robot;
+        let is_wall = self.walls.contains(point);
+        let is_box = self.boxes.contains(point);
+        assert!(
+            [is_robot, is_wall, is_box].iter().filter(|&&x| x).count() <= 1,
+            "At most one of the booleans may be true, but this condition was violated!"
+        );
+        if is_box {
+            'O'
+        } else if is_wall {
+            '#'
+        } else if is_robot {
+            '@'
+        } else {
+            '.'
+        }
+    }
+
+    /// The warehouse as a frame of a recording, with the floor the robot
+    /// stood on marked as `visited`.
+    fn cells(&self, visited: &HashSet<Point>) -> Grid<Cell> {
+        let width = self.walls.iter().map(|p| p.x).max().unwrap() + 1;
+        let height = self.walls.iter().map(|p| p.y).max().unwrap() + 1;
+
+        let rows = (0..height).map(|y| {
+            (0..width).map(|x| {
+                let point = Point::new(x, y);
+                let symbol = self.char_at(&point);
+                let style = match symbol {
+                    '#' => Style::Wall,
+                    '@' => Style::Robot,
+                    '.' if visited.contains(&point) => Style::Visited,
+                    '.' => Style::Floor,
+                    _ => Style::Box,
+                };
+                Cell::new(symbol, style)
+            }).collect()
+        }).collect();
+        Grid::new(rows)
+    }
+
+    fn sum_of_gps_coordinates(&self) -> usize {
+        self.boxes.iter().map(|p| {
+            ((100 * p.y) + p.x) as usize
+        }).sum()
+    }
+
+    fn run_instructions(&mut self) {
+        self.instructions.clone().iter().for_each(|instruction| {
+            self.do_step(instruction);
+        });
+    }
+
+    fn do_step(&mut self, instruction: &char) {
+
+        let mut effected_boxes: Vec<Point> = Vec::new();
+
+        // The moves in the order of the offsets in `DXDYS4`
+        let dxdy = match "<>^v".find(*instruction) {
+            Some(direction) => DXDYS4[direction],
+            None => panic!("Unknown instruction {}", instruction),
+        };
+
+        let mut pointer = self.robot;
+        loop {
+            let next_point = pointer.add(&dxdy);
+            let next_char = self.char_at(&next_point);
+
+            if next_char == '#' {
+                return; // Wall nothing happens
+            } else if next_char == '.' {
+                // Free space perform move
+                for old_box in effected_boxes.iter() {
+                    self.boxes.remove(old_box);
+                }
+                for old_box in effected_boxes.iter() {
+                    self.boxes.insert(old_box.add(&dxdy));
+                }
+                self.robot = self.robot.add(&dxdy);
+                return
+            } else if next_char == 'O' {
+                effected_boxes.push(next_point);
+                pointer = next_point;
+            } else {
+                panic!("Unknown char in map {}", next_char);
+            }
+        }
+
+
+
+    }
+
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let mut parsing_maze = true;
+    let mut y = 0;
+
+    let mut boxes: HashSet<Point> = HashSet::new();
+
+    let mut  walls: HashSet<Point> = HashSet::new();
+
+    let mut  robot: Point = Point::new(0, 0);
+
+    let mut instructions: Vec<char> = Vec::new();
+
+    for line in lines {
+        if line.text.is_empty() {
+            parsing_maze = false;
+            continue;
+        }
+        if parsing_maze {
+            let cells = line.parse_chars("`#`, `O`, `@` or `.`", |c| "#O@.".contains(c).then_some(c))?;
+            cells.into_iter().enumerate().for_each(|(x, input_char)| {
+                let point = Point::new(x as i32, y);
+                match input_char {
+                    'O' => { boxes.insert(point); },
+                    '#' => { walls.insert(point); },
+                    '@' => { robot = point; },
+                    _ => {},
+                }
+            });
+            y += 1;
+        } else {
+            let mut next_instructions =
+                line.parse_chars("a move (`^`, `v`, `<` or `>`)", |c| "^v<>".contains(c).then_some(c))?;
+            instructions.append(&mut next_instructions);
+        }
+    }
+
+    Ok(Problem::new(boxes, walls, robot, instructions))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_15::Day15; test, test_small);
@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, Lines, ParseError, Solution};
+use grid::{Grid, Point};
+use search::Found;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-16"
+    }
+
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+
+    fn record(&self, problem: &Self::Problem, part: u8) -> Option<Recording> {
+        Some(record(problem, part))
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    find_best_paths(problem).cost
+}
+
+fn solve2(problem: &Problem) -> usize {
+    let best_paths = find_best_paths(problem);
+
+    let best_path_tiles: HashSet<Point> =
+        best_paths.paths.on_optimal_paths(best_paths.goals)
+                  .into_iter()
+                  .map(|(point, _)| point)
+                  .collect();
+
+    best_path_tiles.len()
+}
+
+/// The maze with one of the best paths drawn in for part 1, and every tile on
+/// any of the best paths for part 2.
+fn record(problem: &Problem, part: u8) -> Recording {
+    let best_paths = find_best_paths(problem);
+    let mut cells = problem.map.map(|&c| match c {
+        '#' => Cell::new('#', Style::Wall),
+        _ => Cell::FLOOR,
+    });
+
+    let caption = match part {
+        1 => {
+            let path = best_paths.paths.path(&best_paths.goals[0]).unwrap();
+            for pair in path.windows(2) {
+                let (from, (to, _)) = (pair[0].0, pair[1]);
+                if from == to {
+                    continue;
+                }
+                let symbol = match to.minus(&from) {
+                    Point { x: 1, .. } => '>',
+                    Point { x: -1, .. } => '<',
+                    Point { y: 1, .. } => 'v',
+                    _ => '^',
+                };
+                cells.set(&from, Cell::new(symbol, Style::Visited));
+            }
+            format!("a best path, with a score of {}", best_paths.cost)
+        }
+        _ => {
+            let tiles: HashSet<Point> = best_paths.paths.on_optimal_paths(best_paths.goals.clone())
+                                                  .into_iter()
+                                                  .map(|(point, _)| point)
+                                                  .collect();
+            tiles.iter().for_each(|point| cells.set(point, Cell::new('O', Style::Visited)));
+            format!("{} tiles on a best path", tiles.len())
+        }
+    };
+    for point in [problem.map.find(&'S').unwrap(), problem.map.find(&'E').unwrap()] {
+        cells.set(&point, Cell::new(*problem.map.get(&point).unwrap(), Style::Marker));
+    }
+
+    let mut recorder = Recorder::new(format!("day-16 part {}", part));
+    recorder.record(cells, caption);
+    recorder.finish()
+}
+
+fn find_best_paths(problem: &Problem) -> Found<(Point, Direction), usize> {
+    let start_pos =
+        problem.map.find(&'S').unwrap();
+
+    let end_pos =
+        problem.map.find(&'E').unwrap();
+
+    search::astar((start_pos, Direction::East),
+                  |(point, current_direction)| {
+                      [Direction::North, Direction::South, Direction::East, Direction::West]
+                          .into_iter()
+                          .map(|direction| (point.add(&direction.to_dx_dy()), direction))
+                          .filter(|(next_point, _)| problem.map.get(next_point).is_some_and(|c| *c != '#'))
+                          .map(|(next_point, direction)| {
+                              ((next_point, direction), 1 + current_direction.turns_required(direction) * 1000)
+                          })
+                          .collect::<Vec<_>>()
+                  },
+                  |(point, _)| point.distance(&end_pos),
+                  |(point, _)| *point == end_pos).unwrap()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    fn turns_required(self, next_direction: Direction) -> usize {
+        match self {
+            Direction::North => {
+                match next_direction {
+                    Direction::North => 0,
+                    Direction::East => 1,
+                    Direction::South => 2,
+                    Direction::West => 1,
+                }
+            },
+            Direction::East => {
+                match next_direction {
+                    Direction::North => 1,
+                    Direction::East => 0,
+                    Direction::South => 1,
+                    Direction::West => 2,
+                }
+            },
+            Direction::South => {
+                match next_direction {
+                    Direction::North => 2,
+                    Direction::East => 1,
+                    Direction::South => 0,
+                    Direction::West => 1,
+                }
+            },
+            Direction::West => {
+                match next_direction {
+                    Direction::North => 1,
+                    Direction::East => 2,
+                    Direction::South => 1,
+                    Direction::West => 0,
+                }
+            },
+        }
+    }
+
+    fn to_dx_dy(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, 1),
+            Direction::South => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    map: Grid<char>,
+}
+
+impl Problem {
+    fn new(map: Grid<char>) -> Self {
+        Problem { map}
+    }
+}
+
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines
+        .grid_rows("`#`, `.`, `S` or `E`", |c| "#.SE".contains(c).then_some(c))?;
+    Ok(Problem::new(Grid::new(map)))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_16::Day16; test, test2);
@@ -0,0 +1,232 @@
+use itertools::Itertools;
+use common::{Answer, Lines, ParseError, Solution};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-17"
+    }
+
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem.clone()).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        solve2(problem.clone()).map(Answer::from)
+    }
+}
+
+fn solve1(mut problem: Problem) -> String {
+    problem.run();
+    problem.output_as_string()
+}
+
+fn solve2(problem: Problem) -> Option<usize> {
+    let mut digits = problem.program.iter().clone().collect::<Vec<_>>();
+    digits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let candidates =
+    (0..digits.len()).permutations(digits.len()).filter_map(|indices| {
+        let octal_value: String = indices.iter().map(|index| digits.get(*index).unwrap().to_string()).collect();
+        let decimal_value = from_octal(octal_value.as_str()).unwrap();
+        let mut p = problem.clone();
+        p.registers[0] = decimal_value;
+        p.run();
+
+        log::trace!("decimal_value: {:?}, output:{:?} => program:{:?}..", decimal_value, p.output, p.program);
+
+        if p.output == p.program  {
+            Some(decimal_value)
+        }  else {
+            None
+        }
+    }).collect::<Vec<_>>();
+    log::debug!("Candidates => {:?}", candidates);
+    candidates.into_iter().min()
+}
+
+fn from_octal(octal: &str) -> Result<usize, std::num::ParseIntError> {
+    usize::from_str_radix(octal, 8)
+}
+
+// fn to_octal(n: usize) -> String {
+//     format!("{:o}", n)
+// }
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    registers: Vec<usize>,
+    program: Vec<usize>,
+    output: Vec<usize>,
+    pc: usize,
+}
+
+impl Problem {
+    fn new(registers: Vec<usize>,
+           program: Vec<usize>) -> Self {
+        Problem { registers,
+                  program,
+                  output: Vec::new(),
+                  pc: 0 }
+    }
+
+    fn output_as_string(&self) -> String {
+        self.output.iter()
+                   .map(|num| num.to_string())
+                   .collect::<Vec<_>>().join(",")
+    }
+
+    fn run(&mut self) {
+        while self.pc < self.program.len() {
+            self.step()
+        }
+    }
+
+    fn step(&mut self) {
+        let opcode = self.program[self.pc];
+        let operation = Operation::from_opcode(opcode);
+        let operand = self.program[self.pc + 1];
+        match operation {
+            Operation::Adv => {
+                let num = self.registers[0];
+                let demoniator = 2_usize.pow( self.combo_operand_value(operand) as u32);
+                self.registers[0] = num / demoniator;
+                self.pc += 2
+            },
+            Operation::Bxl => {
+                let l = self.registers[1];
+                self.registers[1] = l ^ operand;
+                self.pc += 2
+            },
+            Operation::Bst => {
+                self.registers[1] = self.combo_operand_value(operand)  % 8;
+                self.pc += 2
+            },
+            Operation::Jnz => {
+                if self.registers[0] != 0 {
+                    self.pc = operand
+                } else {
+                    self.pc += 2
+                }
+            },
+            Operation::Bxc => {
+                self.registers[1] ^= self.registers[2];
+                self.pc += 2
+            },
+            Operation::Out => {
+                let res=   self.combo_operand_value(operand ) % 8;
+                self.output.push(res);
+                self.pc += 2
+            },
+            Operation::Bdv => {
+                let num = self.registers[0];
+                let demoniator = 2_usize.pow( self.combo_operand_value(operand) as u32);
+                self.registers[1] = num / demoniator;
+                self.pc += 2
+            },
+            Operation::Cdv => {
+                let num = self.registers[0];
+                let demoniator = 2_usize.pow( self.combo_operand_value(operand) as u32);
+                self.registers[2] = num / demoniator;
+                self.pc += 2
+            },
+        }
+    }
+
+    fn combo_operand_value(&self, operand: usize) -> usize {
+        match operand {
+            0..=3 => { operand }
+            4 => self.registers[0],
+            5 => self.registers[1],
+            6 => self.registers[2],
+            7 => { panic!("reserved") }
+            _ => { panic!("unexpected operand") }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Operation {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+}
+
+impl Operation {
+    fn from_opcode(opcode: usize) -> Self {
+        match opcode {
+            0 => Operation::Adv,
+            1 => Operation::Bxl,
+            2 => Operation::Bst,
+            3 => Operation::Jnz,
+            4 => Operation::Bxc,
+            5 => Operation::Out,
+            6 => Operation::Bdv,
+            7 => Operation::Cdv,
+            _ => panic!("invalid opcode"),
+        }
+    }
+}
+
+
+fn read_input(mut it: Lines) -> Result<Problem, ParseError> {
+
+    let mut registers: Vec<usize> = Vec::new();
+    for name in ["A", "B", "C"] {
+        let line = it.expect(&format!("register {}", name))?;
+        let prefix = format!("Register {}: ", name);
+        let value = line.text.strip_prefix(&prefix).ok_or_else(|| line.error(1, format!("`{}`", prefix)))?;
+        registers.push(line.parse::<usize>(value, "a register value")?);
+    }
+    it.next();
+
+    let line = it.expect("the program")?;
+    let program = line.text.strip_prefix("Program: ").ok_or_else(|| line.error(1, "`Program: `"))?;
+    let program: Vec<usize> =
+        program.split(',')
+               .map(|x| line.parse::<usize>(x, "a 3-bit number"))
+               .collect::<Result<_, _>>()?;
+
+    Ok(Problem::new(registers, program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combo_operands_zero_to_three_are_literals() {
+        let problem = Problem::new(vec![10, 20, 30], Vec::new());
+        (0..=3).for_each(|operand| assert_eq!(problem.combo_operand_value(operand), operand));
+    }
+
+    #[test]
+    fn combo_operands_four_to_six_read_the_registers() {
+        let problem = Problem::new(vec![10, 20, 30], Vec::new());
+        assert_eq!(problem.combo_operand_value(4), 10);
+        assert_eq!(problem.combo_operand_value(5), 20);
+        assert_eq!(problem.combo_operand_value(6), 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "reserved")]
+    fn combo_operand_seven_is_reserved() {
+        Problem::new(vec![10, 20, 30], Vec::new()).combo_operand_value(7);
+    }
+}
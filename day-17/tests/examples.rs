@@ -0,0 +1 @@
+common::example_tests!(day_17::Day17; test, test_repeat);
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+use recording::{Cell, Recorder, Recording, Style};
+use common::{Answer, InputError, Lines, Param, Params, ParseError, Solution};
+use grid::{Grid, Point, DXDYS4};
+use search::Paths;
+
+pub struct Day18;
+
+const PARAMS: &[Param] = &[
+    Param::new("size", 70, "largest x and y coordinate of the memory space"),
+    Param::new("bytes", 1024, "bytes that have fallen in part 1"),
+];
+
+impl Solution for Day18 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-18"
+    }
+
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn configure(&self, problem: &mut Self::Problem, params: &Params) -> Result<(), InputError> {
+        let size = params.get("size");
+        problem.size = i32::try_from(size)
+            .map_err(|_| InputError::Params(format!("`size` must be at most {}, found {}", i32::MAX, size)))?;
+        problem.bytes = params.get("bytes");
+        if problem.bytes > problem.corrupted_bytes.len() {
+            return Err(InputError::Params(format!("`bytes` must be at most {}, the bytes in the input, found {}",
+                                                  problem.corrupted_bytes.len(), problem.bytes)));
+        }
+        Ok(())
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        answer(solve1(problem))
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        answer(solve2(problem))
+    }
+
+    fn record(&self, problem: &Self::Problem, part: u8) -> Option<Recording> {
+        Some(record(problem, part))
+    }
+}
+
+/// The answer to a part, or why the bytes and the size the part was given
+/// leave it without one.
+fn answer(result: Result<impl Into<Answer>, InputError>) -> Option<Answer> {
+    match result {
+        Ok(answer) => Some(answer.into()),
+        Err(error) => {
+            log::warn!("{}", error);
+            None
+        }
+    }
+}
+
+fn solve1(problem: &Problem) -> Result<usize, InputError> {
+
+    let corrupted_bytes = problem.corrupted_bytes
+        .iter()
+        .take(problem.bytes)
+        .collect::<HashSet<_>>();
+
+    do_solve(&corrupted_bytes, problem.size)
+        .ok_or_else(|| InputError::Params(format!("the exit cannot be reached once {} bytes have fallen", problem.bytes)))
+}
+
+fn solve2(problem: &Problem) -> Result<String, InputError> {
+    let mut start = problem.bytes + 1;
+
+    while start <= problem.corrupted_bytes.len() {
+        let corrupted_bytes = problem.corrupted_bytes
+            .iter()
+            .take(start)
+            .collect::<HashSet<_>>();
+
+        let res = do_solve(&corrupted_bytes, problem.size);
+        if res.is_none() {
+            let res = problem.corrupted_bytes[start - 1];
+            return Ok(format!("{},{}", res.x, res.y));
+        }
+        start += 1;
+    }
+
+    Err(InputError::Params(format!("the exit can still be reached once all {} bytes have fallen",
+                                   problem.corrupted_bytes.len())))
+}
+
+/// The bytes that have fallen for part 1 with a shortest path around them.
+/// Part 2 goes on a byte at a time, finding a new path whenever a byte lands
+/// on the old one, up to the byte that cuts off the exit.
+fn record(problem: &Problem, part: u8) -> Recording {
+    let end = Point::new(problem.size, problem.size);
+    let side = problem.size as usize + 1;
+
+    let mut corrupted_bytes: HashSet<&Point> = problem.corrupted_bytes.iter().take(problem.bytes).collect();
+    let mut cells = Grid::filled(side, side, Cell::FLOOR);
+    corrupted_bytes.iter().for_each(|byte| cells.set(byte, Cell::new('#', Style::Wall)));
+
+    let mut recorder = Recorder::new(format!("day-18 part {}", part));
+    let mut path = explore(&corrupted_bytes, problem.size).path(&end).unwrap_or_default();
+    let mut show = |cells: &Grid<Cell>, path: &[Point], caption: String| {
+        let mut cells = cells.clone();
+        path.iter().for_each(|point| cells.set(point, Cell::new('O', Style::Visited)));
+        recorder.record(cells, caption);
+    };
+    show(&cells, &path, format!("{} bytes, {} steps to the exit", problem.bytes, path.len().saturating_sub(1)));
+
+    if part == 2 {
+        for (index, byte) in problem.corrupted_bytes.iter().enumerate().skip(problem.bytes) {
+            corrupted_bytes.insert(byte);
+            cells.set(byte, Cell::new('#', Style::Wall));
+            if path.contains(byte) {
+                path = explore(&corrupted_bytes, problem.size).path(&end).unwrap_or_default();
+            }
+            if path.is_empty() {
+                cells.set(byte, Cell::new('#', Style::Marker));
+                show(&cells, &path, format!("{} bytes, {},{} cuts off the exit", index + 1, byte.x, byte.y));
+                break;
+            }
+            show(&cells, &path, format!("{} bytes, {} steps to the exit", index + 1, path.len() - 1));
+        }
+    }
+    recorder.finish()
+}
+
+fn do_solve(corrupted_bytes: &HashSet<&Point>, size: i32) -> Option<usize> {
+    explore(corrupted_bytes, size).cost(&Point::new(size, size))
+}
+
+/// The shortest paths from the top left corner to every position that can be
+/// reached.
+fn explore(corrupted_bytes: &HashSet<&Point>, size: i32) -> Paths<Point, usize> {
+    let max_x = size;
+    let max_y = size;
+
+    let start = Point::new(0,0);
+
+    search::bfs(start, |position| {
+        DXDYS4.iter()
+             .map(|dxdy| position.add(dxdy))
+             .filter(|p| is_accessible(p, corrupted_bytes, max_x, max_y))
+             .collect::<Vec<_>>()
+    })
+}
+
+fn is_accessible(p: &Point,
+                 corrupted_bytes: &HashSet<&Point>,
+                 max_x: i32,
+                 max_y: i32 ) -> bool {
+    p.x >= 0
+    && p.x <= max_x
+    && p.y >= 0
+    && p.y <= max_y
+    && !corrupted_bytes.contains(p)
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    corrupted_bytes: Vec<Point>,
+    size: i32,
+    bytes: usize,
+}
+
+impl Problem {
+    fn new(corrupted_bytes: Vec<Point>) -> Self {
+        // The size of the memory space and the bytes for part 1 are set by `configure`
+        Problem { corrupted_bytes, size: 0, bytes: 0 }
+    }
+}
+
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let corrupted_bytes: Vec<Point> =
+        lines
+            .map(|line| {
+            let (x, y) = line.split_once(",")?;
+            Ok(Point::new(line.parse(x, "an x coordinate")?, line.parse(y, "a y coordinate")?))
+        }).collect::<Result<_, ParseError>>()?;
+    Ok(Problem::new(corrupted_bytes))
+}
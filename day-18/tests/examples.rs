@@ -0,0 +1 @@
+common::example_tests!(day_18::Day18; test);
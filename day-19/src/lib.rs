@@ -0,0 +1,124 @@
+use std::collections::{HashSet, HashMap};
+use common::{Answer, Lines, ParseError, Solution};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-19"
+    }
+
+    fn day(&self) -> u8 {
+        19
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> usize {
+    problem.designs.iter()
+                                              .filter(|design| {
+                                                  let mut infeasible: HashSet<String> = HashSet::new();
+                                                  is_design_feasible(design,
+                                                                     "".to_string(),
+                                                                     &problem.towels,
+                                                                     &mut infeasible)
+                                              })
+                                              .count()
+}
+
+fn solve2(problem: &Problem) -> usize {
+    problem.designs.iter()
+        .map(|design| {
+            let mut infeasible: HashMap<String, usize> = HashMap::new();
+            count_nr_of_feasible_designs(design,
+                                         "".to_string(),
+                                         &problem.towels,
+                                         &mut infeasible)
+        })
+        .sum::<usize>()
+}
+
+fn is_design_feasible(target: &String,
+                      current: String,
+                      towels: &Vec<String>,
+                      infeasible: &mut  HashSet<String>) -> bool {
+    let r =
+        target == &current
+        || (!infeasible.contains(&current)
+            && target.starts_with(current.as_str()) && towels.iter().any(|towel| {
+            let current =  current.to_string() + towel;
+            is_design_feasible(target, current, towels, infeasible)
+        }));
+
+    if !r  {
+        infeasible.insert(current);
+    }
+
+    r
+}
+
+fn count_nr_of_feasible_designs(target: &String,
+                                current: String,
+                                towels: &Vec<String>,
+                                cache: &mut  HashMap<String, usize>) -> usize {
+    if target == &current {
+        1
+    } else if cache.contains_key(&current) {
+        *cache.get(&current).unwrap()
+    } else {
+        let nr_of_feasible_designs =
+            if target.starts_with(current.as_str()) {
+                towels.iter()
+                      .map(|towel| {
+                            let current =  current.to_string() + towel;
+                            count_nr_of_feasible_designs(target, current, towels, cache)
+                        })
+                     .sum()
+            } else {
+                0
+            };
+        cache.insert(current, nr_of_feasible_designs);
+        nr_of_feasible_designs
+    }
+
+
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    towels: Vec<String>,
+    designs: Vec<String>,
+}
+
+
+impl Problem {
+    fn new(towels: Vec<String>, designs: Vec<String>) -> Self {
+        Self { towels, designs }
+    }
+}
+
+
+fn read_input(mut it: Lines) -> Result<Problem, ParseError> {
+
+    let towels: Vec<String> = it.expect("the available towel patterns")?
+                                .text.split(", ").map(|s| s.to_owned()).collect();
+
+    it.next();
+
+    let designs = it.map(|towel| towel.text).collect();
+
+    Ok(Problem::new(towels, designs))
+}
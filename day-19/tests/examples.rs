@@ -0,0 +1 @@
+common::example_tests!(day_19::Day19; test);
@@ -0,0 +1 @@
+common::example_tests!(day_20_part2::Day20Part2; test);
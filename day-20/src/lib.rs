@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+use common::{Answer, InputError, Lines, Param, Params, ParseError, Solution};
+use grid::{Grid, Point};
+
+pub struct Day20;
+
+const PARAMS: &[Param] = &[
+    Param::new("cheat1", 2, "picoseconds a cheat may last in part 1"),
+    Param::new("cheat2", 20, "picoseconds a cheat may last in part 2"),
+    Param::new("improvement", 100, "picoseconds a cheat has to save at least"),
+];
+
+impl Solution for Day20 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-20"
+    }
+
+    fn day(&self) -> u8 {
+        20
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn configure(&self, problem: &mut Self::Problem, params: &Params) -> Result<(), InputError> {
+        problem.cheats = [params.get("cheat1"), params.get("cheat2")];
+        problem.improvement = params.get("improvement");
+        Ok(())
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(problem, problem.cheats[0], problem.improvement).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(problem, problem.cheats[1], problem.improvement).into())
+    }
+}
+
+fn solve(problem: &Problem, distance_cheat: usize, minimal_improvement: usize) -> usize {
+    let from_start = search::bfs(problem.start_point(), |point| problem.track_next_to(point));
+    let to_end = search::bfs(problem.end_point(), |point| problem.track_next_to(point));
+
+    let normal_length = from_start.cost(&problem.end_point()).unwrap();
+
+    from_start.iter()
+              .map(|(entry_point, length)| {
+                  problem.find_all_points_within_distance_of_point(entry_point, distance_cheat)
+                         .iter()
+                         .filter_map(|exit_point| {
+                             to_end.cost(exit_point)
+                                   .map(|rest| length + entry_point.distance(exit_point) + rest)
+                         })
+                         .filter(|cheated_length| cheated_length + minimal_improvement <= normal_length)
+                         .count()
+              })
+              .sum()
+}
+
+#[derive(Clone)]
+pub struct Problem {
+    map: Grid<char>,
+    cheats: [usize; 2],
+    improvement: usize,
+}
+
+impl Problem {
+    fn new(map: Grid<char>) -> Self {
+        // The cheats and the improvement are set by `configure`
+        Problem { map, cheats: [0, 0], improvement: 0 }
+    }
+
+    fn start_point (&self) -> Point {
+        self.map.find(&'S').unwrap()
+    }
+
+    fn end_point (&self) -> Point {
+        self.map.find(&'E').unwrap()
+    }
+
+    fn track_next_to(&self, point: &Point) -> Vec<Point> {
+        self.map.neighbours4(point)
+                .filter(|next_point| self.map.get(next_point) != Some(&'#'))
+                .collect()
+    }
+
+
+    fn find_all_points_within_distance_of_point(&self, point: &Point, max_distance: usize) -> Vec<Point> {
+        let mut results = Vec::new();
+
+        // Up and Right
+        for dx in 0..=max_distance as i32 {
+            for dy in 0..=(max_distance as i32 - dx) {
+                results.push(Point::new(point.x + dx, point.y + dy));
+            }
+        }
+
+        // Up and Left
+        for dx in 0..=max_distance as i32 {
+            for dy in 0..=(max_distance as i32 - dx) {
+                results.push(Point::new(point.x - dx, point.y + dy));
+            }
+        }
+
+        // Down and Left
+        for dx in 0..=max_distance as i32 {
+            for dy in 0..=(max_distance as i32 - dx) {
+                results.push(Point::new(point.x - dx, point.y - dy));
+            }
+        }
+
+        // Down and Right
+        for dx in 0..=max_distance as i32 {
+            for dy in 0..=(max_distance as i32 - dx) {
+                results.push(Point::new(point.x + dx, point.y - dy));
+            }
+        }
+
+        let r: HashSet<Point> =
+        results.into_iter().filter(|p|
+                              p != point
+                              && self.map.is_on_map(p)
+                              && self.map.get(p) != Some(&'#')).collect();
+
+        let r = r.into_iter().collect();
+
+        // println!("point: {:?} => {:?} ..", point, r);
+
+        r
+    }
+
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let map = lines
+        .grid_rows("`#`, `.`, `S` or `E`", |c| "#.SE".contains(c).then_some(c))?;
+    Ok(Problem::new(Grid::new(map)))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_20::Day20; test);
@@ -0,0 +1 @@
+common::example_tests!(day_21::Day21; test, test_small);
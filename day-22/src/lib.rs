@@ -0,0 +1,209 @@
+use common::{Answer, InputError, Lines, Param, Params, ParseError, Solution};
+
+pub struct Day22;
+
+const PARAMS: &[Param] = &[
+    Param::new("secrets", 2000, "new secret numbers every buyer generates"),
+];
+
+impl Solution for Day22 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-22"
+    }
+
+    fn day(&self) -> u8 {
+        22
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn configure(&self, problem: &mut Self::Problem, params: &Params) -> Result<(), InputError> {
+        problem.secrets = params.get("secrets");
+        Ok(())
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve1(problem).into())
+    }
+
+    fn solve2(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve2(problem).into())
+    }
+}
+
+fn solve1(problem: &Problem) -> i64 {
+    problem.secret_numbers.iter()
+                              .map(|secret| {
+                                let mut secret = *secret;
+                                for _ in 0..problem.secrets {
+                                    secret = secret_to_secret(secret);
+                                }
+                                secret
+                              }).sum::<i64>()
+}
+
+fn solve2(problem: &Problem) -> i64 {
+
+    let secrets = generate_secrets(problem);
+
+    let price_difference = generate_price_differences(&secrets);
+
+    generate_combinations().map(|sequence| {
+        apply_sequence_to_problem(&sequence,
+                                  &secrets,
+                                  &price_difference)
+    }).max().unwrap()
+}
+
+fn generate_combinations() -> impl Iterator<Item = [i64; 4]> {
+    (-9..=9)
+        .flat_map(|a| {
+            (-9..=9).flat_map(move |b| {
+                (-9..=9).flat_map(move |c| {
+                    (-9..=9).map(move |d| [a, b, c, d])
+                })
+            })
+        })
+}
+
+fn generate_price_differences(secrets: &[Vec<i64>]) -> Vec<Vec<i64>> {
+    let mut res = Vec::new();
+
+    for secret in secrets {
+        let last_numbers =
+            secret.iter()
+                  .map(|secret|secret_to_price(*secret))
+                  .collect::<Vec<i64>>();
+
+        let differences: Vec<i64> =
+            last_numbers.iter().zip(last_numbers.iter().skip(1)).map(|(a, b)| b - a).collect();
+
+        res.push(differences)
+    }
+
+    res
+}
+
+fn generate_secrets(problem: &Problem) -> Vec<Vec<i64>> {
+    let mut res = Vec::new();
+    for secret in &problem.secret_numbers {
+        let mut secrets = Vec::new();
+        secrets.push(*secret);
+
+        let mut secret = *secret;
+        for _ in 0..problem.secrets {
+            secret = secret_to_secret(secret);
+            secrets.push(secret);
+        }
+        res.push(secrets);
+    }
+
+    res
+}
+
+fn secret_to_price(secret: i64) -> i64 {
+    secret.to_string().chars().last().unwrap().to_string().parse::<i64>().unwrap()
+}
+
+fn apply_sequence_to_problem(sequence: &[i64; 4],
+                             secrets: &[Vec<i64>],
+                             price_difference: &[Vec<i64>]) -> i64 {
+    let mut res = 0_i64;
+    for index in 0..secrets.len() {
+        let monkey_secrets = &secrets[index];
+        let monkey_price_differences = &price_difference[index];
+        let monkey_res =
+            apply_sequence_to_monkey(sequence,
+                                     monkey_secrets,
+                                     monkey_price_differences).unwrap_or(0);
+        res += monkey_res;
+    }
+    res
+}
+
+fn apply_sequence_to_monkey(sequence: &[i64; 4],
+                            monkey_secrets: &[i64],
+                            monkey_price_differences: &[i64]) -> Option<i64> {
+
+    let index =
+        monkey_price_differences.windows(sequence.len())
+                        .enumerate()
+                        .find(|(_, window)| *window == sequence)
+                        .map(|(index, _)| index)? + sequence.len();
+
+
+    Some(secret_to_price(monkey_secrets[index]))
+}
+
+fn secret_to_secret(secret_number: i64) -> i64 {
+    let mut new_secret = secret_number;
+
+    let multiplied_value = new_secret * 64;
+    new_secret = mix_into(new_secret, multiplied_value);
+    new_secret = prune(new_secret);
+
+    let divided_value = new_secret / 32;
+    new_secret = mix_into(new_secret, divided_value);
+    new_secret = prune(new_secret);
+
+    let multiplied_value = new_secret * 2048;
+    new_secret = mix_into(new_secret, multiplied_value);
+    new_secret = prune(new_secret);
+
+    new_secret
+}
+
+
+fn mix_into(secret_number: i64, value: i64) -> i64 {
+    secret_number ^ value
+}
+
+fn prune(secret_number: i64) -> i64 {
+    secret_number % 16777216
+}
+
+pub struct Problem {
+    secret_numbers: Vec<i64>,
+    secrets: usize,
+}
+
+impl Problem {
+    fn new(secret_numbers: Vec<i64>) -> Self {
+        // The number of secrets is set by `configure`
+        Problem { secret_numbers, secrets: 0 }
+    }
+}
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    Ok(Problem::new( lines.map(|line| line.parse::<i64>(&line.text, "a secret number"))
+                                          .collect::<Result<_, ParseError>>()? ) )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_to_secret_follows_the_example_sequence() {
+        let expected = [15887950, 16495136, 527345, 704524, 1553684,
+                        12683156, 11100544, 12249484, 7753432, 5908254];
+        let mut secret = 123;
+        for next in expected {
+            secret = secret_to_secret(secret);
+            assert_eq!(secret, next);
+        }
+    }
+
+    #[test]
+    fn two_thousand_secrets_of_the_first_buyer() {
+        assert_eq!((0..2000).fold(1, |secret, _| secret_to_secret(secret)), 8685429);
+    }
+}
@@ -0,0 +1 @@
+common::example_tests!(day_22::Day22; test);
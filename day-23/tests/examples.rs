@@ -0,0 +1 @@
+common::example_tests!(day_23::Day23; test);
@@ -0,0 +1 @@
+common::example_tests!(day_24::Day24; test, test_small);
@@ -0,0 +1,103 @@
+use common::{Answer, Lines, ParseError, Solution};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Problem = Problem;
+
+    fn name(&self) -> &'static str {
+        "day-25"
+    }
+
+    fn day(&self) -> u8 {
+        25
+    }
+
+    fn parts(&self) -> &'static [u8] {
+        &[1]
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Self::Problem, ParseError> {
+        read_input(lines)
+    }
+
+    fn solve1(&self, problem: &Self::Problem) -> Option<Answer> {
+        Some(solve(problem).into())
+    }
+}
+
+fn solve(problem: &Problem) -> usize {
+    let locks = problem.locks();
+    let keys = problem.keys();
+    locks.iter().map(|lock| {
+            keys.iter().filter(|key| {
+                                (0..key.len()).all(
+                                        |i| {
+                                            lock.get(i).unwrap() + key.get(i).unwrap() < 6
+                                        }
+                                )}
+            ).count()
+        }).sum::<usize>()
+}
+
+pub struct Problem {
+    input: Vec<Vec<Vec<char>>>
+}
+impl Problem {
+    fn new(input: Vec<Vec<Vec<char>>>) -> Self {
+        Problem { input }
+    }
+
+    fn locks(&self) -> Vec<Vec<usize>> {
+        self.input.iter()
+                  .filter(|candidate|
+                      { candidate.first().unwrap().iter().all(|item| *item == '#' ) }
+                  )
+                  .map(|lock|
+                        (0..lock.first().unwrap().len()).map(|x|
+                            (0..lock.len()).find(|y |
+                                lock.get(*y)
+                                    .unwrap()
+                                    .get(x) == Some(&'.'))
+                                .map(|y| y - 1)
+                                .unwrap()).collect()
+                    )
+                    .collect()
+    }
+
+
+    fn keys(&self) -> Vec<Vec<usize>> {
+        self.input.iter()
+            .filter(|candidate|
+                { candidate.last().unwrap().iter().all(|item| *item == '#') }
+            )
+            .map(|key|
+                (0..key.first().unwrap().len()).map(|x|
+                    (0..key.len()).find(|y|
+                        key.get(*y)
+                            .unwrap()
+                            .get(x) == Some(&'#'))
+                        .map(|y| key.len() - 1 - y)
+                        .unwrap()).collect()
+            )
+            .collect()
+    }
+}
+
+
+fn read_input(lines: Lines) -> Result<Problem, ParseError> {
+    let mut input: Vec<Vec<Vec<char>>> = Vec::new();
+    let mut current: Vec<Vec<char>> = Vec::new();
+
+    for line in lines {
+        if line.text.is_empty() {
+            input.push(current.clone());
+            current.clear();
+            continue;
+        }
+        current.push(line.parse_chars("`#` or `.`", |c| "#.".contains(c).then_some(c))?);
+    }
+    input.push(current.clone());
+
+    Ok(Problem::new(input))
+}
@@ -0,0 +1 @@
+common::example_tests!(day_25::Day25; test);
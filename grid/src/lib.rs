@@ -0,0 +1,206 @@
+mod point;
+
+use std::fmt;
+
+pub use point::{Point, DXDYS4, DXDYS8};
+
+/// A rectangular map of cells, as found in most of the puzzle inputs.
+///
+/// Cells are stored row by row, so `Grid<char>` holds the letters of day 04's
+/// word search just as well as `Grid<usize>` holds day 10's heights.
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows. Panics if the rows differ in length.
+    pub fn new(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == width),
+                "All rows of a grid must have the same length");
+
+        Grid { cells: rows.into_iter().flatten().collect(), width, height }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width as i32
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height as i32
+    }
+
+    pub fn is_on_map(&self, point: &Point) -> bool {
+        point.x < self.width() && point.x >= 0 && point.y >= 0 && point.y < self.height()
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Replaces the cell at `point`. Panics if the point is not on the map.
+    pub fn set(&mut self, point: &Point, value: T) {
+        let index = self.index(point).expect("Point is not on the map");
+        self.cells[index] = value;
+    }
+
+    /// All points of the map, row by row.
+    pub fn iter_points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height()).flat_map(move |y| {
+            (0..self.width()).map(move |x| Point::new(x, y))
+        })
+    }
+
+    /// All points of the map together with their cell.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.iter_points().zip(self.cells.iter())
+    }
+
+    /// The first point, row by row, whose cell equals `value`.
+    pub fn find(&self, value: &T) -> Option<Point> where T: PartialEq {
+        self.iter().find(|(_, cell)| *cell == value).map(|(point, _)| point)
+    }
+
+    /// The orthogonal neighbours of `point` that are on the map.
+    pub fn neighbours4<'a>(&'a self, point: &'a Point) -> impl Iterator<Item = Point> + 'a {
+        point.neighbours4().filter(move |neighbour| self.is_on_map(neighbour))
+    }
+
+    /// All neighbours of `point`, diagonal ones included, that are on the map.
+    pub fn neighbours8<'a>(&'a self, point: &'a Point) -> impl Iterator<Item = Point> + 'a {
+        point.neighbours8().filter(move |neighbour| self.is_on_map(neighbour))
+    }
+
+    /// Walks from `start` in steps of `dxdy` until leaving the map. The start
+    /// itself is the first point, provided it is on the map.
+    pub fn ray(&self, start: &Point, dxdy: &Point) -> impl Iterator<Item = Point> + '_ {
+        let dxdy = *dxdy;
+        std::iter::successors(Some(*start), move |point| Some(point.add(&dxdy)))
+            .take_while(move |point| self.is_on_map(point))
+    }
+
+    /// The cells met by [`Grid::ray`], `None` when the ray leaves the map before
+    /// `length` cells were seen.
+    pub fn ray_cells(&self, start: &Point, dxdy: &Point, length: usize) -> Option<Vec<&T>> {
+        let cells: Vec<&T> = self.ray(start, dxdy)
+                                 .take(length)
+                                 .map(|point| self.get(&point).unwrap())
+                                 .collect();
+        if cells.len() == length { Some(cells) } else { None }
+    }
+
+    /// Applies `f` to every cell, keeping the shape of the grid.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    fn index(&self, point: &Point) -> Option<usize> {
+        if self.is_on_map(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| Point::new(y, x))
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height();
+        self.remap(self.height, self.width, |x, y| Point::new(y, height - 1 - x))
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let width = self.width();
+        self.remap(self.height, self.width, |x, y| Point::new(width - 1 - y, x))
+    }
+
+    /// Builds a `width` by `height` grid whose cell `(x, y)` is this grid's cell
+    /// at `source(x, y)`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(i32, i32) -> Point) -> Self {
+        let cells = (0..height as i32).flat_map(|y| (0..width as i32).map(move |x| (x, y)))
+                                      .map(|(x, y)| self.get(&source(x, y)).unwrap().clone())
+                                      .collect();
+        Grid { cells, width, height }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(rows: &[&str]) -> Grid<char> {
+        Grid::new(rows.iter().map(|row| row.chars().collect()).collect())
+    }
+
+    #[test]
+    fn transposing_turns_rows_into_columns() {
+        let grid = letters(&["abc", "def"]);
+        assert_eq!(grid.transpose(), letters(&["ad", "be", "cf"]));
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn rotations_turn_the_grid_a_quarter() {
+        let grid = letters(&["abc", "def"]);
+        let clockwise = grid.rotate_clockwise();
+        assert_eq!(clockwise, letters(&["da", "eb", "fc"]));
+        assert_eq!((clockwise.width(), clockwise.height()), (2, 3));
+        assert_eq!(grid.rotate_counterclockwise(), letters(&["cf", "be", "ad"]));
+
+        assert_eq!(clockwise.rotate_counterclockwise(), grid);
+        assert_eq!(clockwise.rotate_clockwise(), letters(&["fed", "cba"]));
+        assert_eq!((0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise()), grid);
+    }
+
+    #[test]
+    fn rays_stop_at_the_edge_of_the_map() {
+        let grid = letters(&["abc", "def"]);
+        let ray: Vec<Point> = grid.ray(&Point::new(0, 0), &Point::new(1, 1)).collect();
+        assert_eq!(ray, vec![Point::new(0, 0), Point::new(1, 1)]);
+        let ray: Vec<Point> = grid.ray(&Point::new(2, 1), &Point::new(-1, 0)).collect();
+        assert_eq!(ray, vec![Point::new(2, 1), Point::new(1, 1), Point::new(0, 1)]);
+        assert_eq!(grid.ray(&Point::new(3, 0), &Point::new(-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn ray_cells_need_the_whole_length_on_the_map() {
+        let grid = letters(&["abc", "def"]);
+        assert_eq!(grid.ray_cells(&Point::new(0, 0), &Point::new(1, 0), 3), Some(vec![&'a', &'b', &'c']));
+        assert_eq!(grid.ray_cells(&Point::new(2, 1), &Point::new(-1, -1), 2), Some(vec![&'f', &'b']));
+        assert_eq!(grid.ray_cells(&Point::new(2, 1), &Point::new(-1, -1), 3), None);
+        assert_eq!(grid.ray_cells(&Point::new(0, 2), &Point::new(1, 0), 1), None);
+        assert_eq!(grid.ray_cells(&Point::new(1, 0), &Point::new(0, 1), 0), Some(vec![]));
+    }
+}
@@ -0,0 +1,62 @@
+/// A location on a [`crate::Grid`], or an offset between two of them.
+///
+/// `x` grows to the right and `y` grows downwards, matching the order in which
+/// the puzzle maps are read.
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Offsets to the four orthogonal neighbours: left, right, up and down.
+pub const DXDYS4: [Point; 4] = [
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(0, -1),
+    Point::new(0, 1),
+];
+
+/// Offsets to all eight neighbours, the orthogonal ones first.
+pub const DXDYS8: [Point; 8] = [
+    Point::new(-1, 0),
+    Point::new(1, 0),
+    Point::new(0, -1),
+    Point::new(0, 1),
+    Point::new(-1, -1),
+    Point::new(1, -1),
+    Point::new(-1, 1),
+    Point::new(1, 1),
+];
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn add(&self, other: &Point) -> Self {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+
+    pub fn minus(&self, other: &Point) -> Self {
+        Point { x: self.x - other.x, y: self.y - other.y }
+    }
+
+    pub fn mul(&self, factor: i32) -> Self {
+        Point { x: self.x * factor, y: self.y * factor }
+    }
+
+    /// Manhattan distance between both points.
+    pub fn distance(&self, other: &Point) -> usize {
+        (self.x.abs_diff(other.x) + self.y.abs_diff(other.y)) as usize
+    }
+
+    /// The four orthogonal neighbours, whether or not they are on a map.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Point> + '_ {
+        DXDYS4.iter().map(move |dxdy| self.add(dxdy))
+    }
+
+    /// All eight neighbours, whether or not they are on a map.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Point> + '_ {
+        DXDYS8.iter().map(move |dxdy| self.add(dxdy))
+    }
+}
@@ -0,0 +1,229 @@
+//! The frames of a simulation, as days record them for the `animation`
+//! crate to play and export. It only depends on `grid`, so that solving a
+//! day does not pull in a terminal or image encoders.
+
+use grid::{Grid, Point};
+
+/// What a cell shows, which decides its colour when a frame is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// Empty space.
+    Floor,
+    Wall,
+    Box,
+    /// Whatever moves around: a robot, the guard.
+    Robot,
+    /// A tile that was stepped on before.
+    Visited,
+    /// Anything a day wants to stand out, such as an added obstruction.
+    Marker,
+    /// One of a handful of colours for telling regions of a map apart. Days
+    /// pick the numbers so that neighbouring regions differ.
+    Region(usize),
+}
+
+/// The colours of the regions, chosen to be easy to tell apart.
+pub const REGION_COLOURS: [[u8; 3]; 12] = [
+    [230, 25, 75], [60, 180, 75], [255, 225, 25], [0, 130, 200], [245, 130, 48], [145, 30, 180],
+    [70, 240, 240], [240, 50, 230], [210, 245, 60], [250, 190, 212], [0, 128, 128], [220, 190, 255],
+];
+
+impl Style {
+    /// The colour of the style in images, as red, green and blue.
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Style::Floor => [16, 16, 24],
+            Style::Wall => [110, 110, 120],
+            Style::Box => [214, 170, 40],
+            Style::Robot => [80, 200, 90],
+            Style::Visited => [70, 150, 230],
+            Style::Marker => [230, 60, 90],
+            Style::Region(region) => REGION_COLOURS[region % REGION_COLOURS.len()],
+        }
+    }
+}
+
+/// A single character of a frame together with its style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub symbol: char,
+    pub style: Style,
+}
+
+impl Cell {
+    pub const FLOOR: Cell = Cell::new('.', Style::Floor);
+
+    pub const fn new(symbol: char, style: Style) -> Self {
+        Cell { symbol, style }
+    }
+}
+
+/// Every how many frames a recording keeps a full copy of the grid, so
+/// seeking never has to replay more than this many changes.
+const KEYFRAME_INTERVAL: usize = 256;
+
+/// The states of a simulation, one frame each, as collected by a [`Recorder`].
+///
+/// Frames are stored as the cells that changed since the frame before, which
+/// keeps the thousands of frames of a puzzle input small as most steps only
+/// move a few cells.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    title: String,
+    keyframes: Vec<Grid<Cell>>,
+    frames: Vec<Frame>,
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    caption: String,
+    /// The cells that differ from the frame before, empty for keyframes.
+    changes: Vec<(Point, Cell)>,
+}
+
+impl Recording {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// What was happening in frame `index`, e.g. the move that was made.
+    pub fn caption(&self, index: usize) -> &str {
+        &self.frames[index].caption
+    }
+
+    /// The cells of frame `index`. Panics if there is no such frame.
+    pub fn frame(&self, index: usize) -> Grid<Cell> {
+        let keyframe = index / KEYFRAME_INTERVAL;
+        let mut cells = self.keyframes[keyframe].clone();
+        for next in keyframe * KEYFRAME_INTERVAL + 1..=index {
+            self.advance(next, &mut cells);
+        }
+        cells
+    }
+
+    /// Turns `cells`, which show frame `index - 1`, into frame `index`. This is
+    /// what plays a recording forward without copying whole grids.
+    pub fn advance(&self, index: usize, cells: &mut Grid<Cell>) {
+        if index.is_multiple_of(KEYFRAME_INTERVAL) {
+            *cells = self.keyframes[index / KEYFRAME_INTERVAL].clone();
+        } else {
+            for (point, cell) in &self.frames[index].changes {
+                cells.set(point, *cell);
+            }
+        }
+    }
+}
+
+/// Collects the frames of a simulation while it runs.
+///
+/// ```ignore
+/// let mut recorder = Recorder::new("day-15 part 1");
+/// for (index, instruction) in instructions.iter().enumerate() {
+///     problem.do_step(instruction);
+///     recorder.record(problem.cells(), format!("move {}: {}", index + 1, instruction));
+/// }
+/// let recording = recorder.finish();
+/// ```
+#[derive(Debug)]
+pub struct Recorder {
+    recording: Recording,
+    last: Option<Grid<Cell>>,
+}
+
+impl Recorder {
+    pub fn new(title: impl Into<String>) -> Self {
+        Recorder {
+            recording: Recording { title: title.into(), keyframes: Vec::new(), frames: Vec::new() },
+            last: None,
+        }
+    }
+
+    /// Adds a frame. Every frame of a recording must have the same size as the
+    /// first one.
+    pub fn record(&mut self, cells: Grid<Cell>, caption: impl Into<String>) {
+        let caption = caption.into();
+        let frames = &mut self.recording.frames;
+        match &self.last {
+            Some(last) => {
+                assert!(last.width() == cells.width() && last.height() == cells.height(),
+                        "All frames of a recording must have the same size");
+                let changes = if frames.len().is_multiple_of(KEYFRAME_INTERVAL) {
+                    self.recording.keyframes.push(cells.clone());
+                    Vec::new()
+                } else {
+                    cells.iter()
+                         .zip(last.iter())
+                         .filter(|((_, cell), (_, before))| cell != before)
+                         .map(|((point, cell), _)| (point, *cell))
+                         .collect()
+                };
+                frames.push(Frame { caption, changes });
+            }
+            None => {
+                self.recording.keyframes.push(cells.clone());
+                frames.push(Frame { caption, changes: Vec::new() });
+            }
+        }
+        self.last = Some(cells);
+    }
+
+    pub fn len(&self) -> usize {
+        self.recording.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recording.is_empty()
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A robot walking right along a row of ten cells, leaving visited tiles.
+    fn walk(steps: usize) -> Vec<Grid<Cell>> {
+        (0..steps).map(|step| {
+                      let mut cells = Grid::filled(10, 1, Cell::FLOOR);
+                      for x in 0..step % 10 {
+                          cells.set(&Point::new(x as i32, 0), Cell::new('X', Style::Visited));
+                      }
+                      cells.set(&Point::new((step % 10) as i32, 0), Cell::new('@', Style::Robot));
+                      cells
+                  })
+                  .collect()
+    }
+
+    #[test]
+    fn frames_are_rebuilt_from_their_changes() {
+        let frames = walk(KEYFRAME_INTERVAL * 2 + 7);
+        let mut recorder = Recorder::new("walk");
+        for (index, frame) in frames.iter().enumerate() {
+            recorder.record(frame.clone(), format!("step {}", index));
+        }
+        let recording = recorder.finish();
+        assert_eq!(recording.len(), frames.len());
+        assert_eq!(recording.caption(300), "step 300");
+
+        for index in [0, 1, 9, 10, KEYFRAME_INTERVAL - 1, KEYFRAME_INTERVAL, frames.len() - 1] {
+            assert_eq!(recording.frame(index), frames[index], "frame {}", index);
+        }
+
+        let mut cells = recording.frame(0);
+        for index in 1..frames.len() {
+            recording.advance(index, &mut cells);
+        }
+        assert_eq!(cells, frames[frames.len() - 1]);
+    }
+}
@@ -0,0 +1,274 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cost of a step or a path. `Default` is the cost of the empty path.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// The outcome of a search: the cost of the best path to every state that was
+/// reached, and every state that precedes it on one of those best paths.
+///
+/// Keeping all optimal predecessors, instead of just one, is what lets days
+/// ask for every tile on any best path or for the number of best paths.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Cost> Paths<S, C> {
+    fn new(start: S) -> Self {
+        Paths {
+            costs: HashMap::from([(start.clone(), C::default())]),
+            predecessors: HashMap::from([(start, Vec::new())]),
+        }
+    }
+
+    /// Records that `state` can be reached from `from` at `cost`. Returns
+    /// whether that is strictly better than what was known before.
+    fn relax(&mut self, from: &S, state: S, cost: C) -> bool {
+        match self.costs.get(&state) {
+            Some(&known) if known < cost => false,
+            Some(&known) if known == cost => {
+                let predecessors = self.predecessors.get_mut(&state).unwrap();
+                if !predecessors.contains(from) {
+                    predecessors.push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.costs.insert(state.clone(), cost);
+                self.predecessors.insert(state, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    /// The cost of the best path to `state`, `None` when it was not reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// Every state that was reached, with the cost of the best path to it.
+    pub fn iter(&self) -> impl Iterator<Item = (&S, C)> + '_ {
+        self.costs.iter().map(|(state, cost)| (state, *cost))
+    }
+
+    /// The states directly before `state` on its best paths, empty for the
+    /// start and for states that were not reached.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], |predecessors| predecessors)
+    }
+
+    /// One of the best paths to `goal`, from the start up to and including
+    /// `goal`.
+    pub fn path(&self, goal: &S) -> Option<Vec<S>> {
+        self.costs.get(goal)?;
+        let mut path = vec![goal.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state on any of the best paths to any of the `goals`.
+    pub fn on_optimal_paths(&self, goals: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut states: HashSet<S> = HashSet::new();
+        let mut to_do: Vec<S> = goals.into_iter().filter(|goal| self.costs.contains_key(goal)).collect();
+        while let Some(state) = to_do.pop() {
+            if states.insert(state.clone()) {
+                to_do.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        states
+    }
+
+    /// The number of different best paths to `goal`. Only meaningful when
+    /// every step costs more than nothing.
+    pub fn count_optimal_paths(&self, goal: &S) -> usize {
+        let mut states: Vec<S> = self.on_optimal_paths([goal.clone()]).into_iter().collect();
+        // A predecessor is always cheaper, so this counts every state after all of its predecessors
+        states.sort_by_key(|state| self.costs[state]);
+
+        let mut counts: HashMap<&S, usize> = HashMap::new();
+        for state in &states {
+            let predecessors = self.predecessors(state);
+            let count = if predecessors.is_empty() {
+                1
+            } else {
+                predecessors.iter().map(|predecessor| counts[predecessor]).sum()
+            };
+            counts.insert(state, count);
+        }
+        counts.get(goal).copied().unwrap_or(0)
+    }
+}
+
+/// Breadth first search from `start`, where every step costs 1. Explores
+/// everything that can be reached.
+pub fn bfs<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> Paths<S, usize>
+    where S: Clone + Eq + Hash,
+          I: IntoIterator<Item = S> {
+    let mut paths = Paths::new(start.clone());
+    let mut queue: VecDeque<S> = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let cost = paths.costs[&state] + 1;
+        for next in successors(&state) {
+            if paths.relax(&state, next.clone(), cost) {
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm from `start`, where `successors` gives the states
+/// one step away together with the cost of that step. Explores everything
+/// that can be reached.
+pub fn dijkstra<S, C, I>(start: S, successors: impl FnMut(&S) -> I) -> Paths<S, C>
+    where S: Clone + Eq + Hash,
+          C: Cost,
+          I: IntoIterator<Item = (S, C)> {
+    best_first(start, successors, |_| C::default(), |_| false).paths
+}
+
+/// The best paths from `start` to the states for which `is_goal` holds.
+#[derive(Debug, Clone)]
+pub struct Found<S, C> {
+    pub cost: C,
+    /// The goals that were reached at `cost`.
+    pub goals: Vec<S>,
+    /// Everything explored on the way, which includes all best paths to the
+    /// `goals`.
+    pub paths: Paths<S, C>,
+}
+
+/// A* search from `start` to the cheapest state for which `is_goal` holds.
+/// The `heuristic` estimates the cost of the rest of the way and must never
+/// overestimate it. It need not be consistent: a state that turns out to be
+/// cheaper to reach after it was explored is explored again. The search goes
+/// on until every path as cheap as the best one has been found, so ties
+/// between goals and predecessors are complete.
+pub fn astar<S, C, I>(start: S,
+                      successors: impl FnMut(&S) -> I,
+                      heuristic: impl FnMut(&S) -> C,
+                      is_goal: impl FnMut(&S) -> bool) -> Option<Found<S, C>>
+    where S: Clone + Eq + Hash,
+          C: Cost,
+          I: IntoIterator<Item = (S, C)> {
+    let search = best_first(start, successors, heuristic, is_goal);
+    let cost = search.cost?;
+    Some(Found { cost, goals: search.goals, paths: search.paths })
+}
+
+struct Search<S, C> {
+    paths: Paths<S, C>,
+    cost: Option<C>,
+    goals: Vec<S>,
+}
+
+fn best_first<S, C, I>(start: S,
+                       mut successors: impl FnMut(&S) -> I,
+                       mut heuristic: impl FnMut(&S) -> C,
+                       mut is_goal: impl FnMut(&S) -> bool) -> Search<S, C>
+    where S: Clone + Eq + Hash,
+          C: Cost,
+          I: IntoIterator<Item = (S, C)> {
+    let mut search = Search { paths: Paths::new(start.clone()), cost: None, goals: Vec::new() };
+    let mut done: HashSet<S> = HashSet::new();
+
+    // The heap holds indices into `states`, so states do not have to be ordered themselves
+    let mut states: Vec<S> = vec![start.clone()];
+    let mut to_do: BinaryHeap<Reverse<(C, C, usize)>> = BinaryHeap::new();
+    to_do.push(Reverse((heuristic(&start), C::default(), 0)));
+
+    while let Some(Reverse((estimate, cost, index))) = to_do.pop() {
+        if search.cost.is_some_and(|best| estimate > best) {
+            break;
+        }
+        let state = states[index].clone();
+        if cost > search.paths.costs[&state] || !done.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            search.cost = Some(cost);
+            search.goals.push(state);
+            continue;
+        }
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if search.paths.relax(&state, next.clone(), next_cost) {
+                // Only an inconsistent heuristic finds a cheaper way to a state that is done
+                done.remove(&next);
+                to_do.push(Reverse((next_cost + heuristic(&next), next_cost, states.len())));
+                states.push(next);
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A diamond with two equally short ways from 0 to 3, and a longer one
+    /// through 4.
+    fn diamond(state: &u32) -> Vec<(u32, u32)> {
+        match state {
+            0 => vec![(1, 1), (2, 1), (4, 1)],
+            1 | 2 => vec![(3, 1)],
+            4 => vec![(3, 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn all_optimal_predecessors_are_kept() {
+        let paths = dijkstra(0, diamond);
+        assert_eq!(paths.cost(&3), Some(2));
+        assert_eq!(paths.predecessors(&3), &[1, 2]);
+        assert_eq!(paths.count_optimal_paths(&3), 2);
+        assert_eq!(paths.on_optimal_paths([3]), HashSet::from([0, 1, 2, 3]));
+        assert_eq!(paths.path(&3), Some(vec![0, 1, 3]));
+    }
+
+    #[test]
+    fn astar_finds_every_goal_at_the_best_cost() {
+        let found = astar(0, |state: &i32| [(state + 1, 1), (state + 2, 3)], |state| (10 - state).max(0), |state| *state >= 10)
+            .unwrap();
+        assert_eq!(found.cost, 10);
+        assert_eq!(found.goals, vec![10]);
+        assert_eq!(found.paths.count_optimal_paths(&10), 1);
+    }
+
+    #[test]
+    fn astar_reopens_states_an_inconsistent_heuristic_explored_too_early() {
+        // The heuristic overrates 1, so 2 is explored through the expensive
+        // step first and has to be explored again once 1 finds it cheaper
+        let successors = |state: &u32| match state {
+            0 => vec![(1, 1), (2, 4)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 4)],
+            _ => vec![],
+        };
+        let heuristic = |state: &u32| if *state == 1 { 5 } else { 0 };
+        let found = astar(0, successors, heuristic, |state| *state == 3).unwrap();
+        assert_eq!(found.cost, 6);
+        assert_eq!(found.paths.path(&3), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let paths = bfs(0, |state: &i32| [state - 1, state + 1].into_iter().filter(|next| next.abs() <= 3));
+        assert_eq!(paths.cost(&-3), Some(3));
+        assert_eq!(paths.iter().count(), 7);
+        assert_eq!(paths.cost(&4), None);
+    }
+}